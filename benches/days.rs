@@ -0,0 +1,61 @@
+use aoc_2020::{
+    inputs::{InputCache, InputKind},
+    problems, DayContext, Part, Parts, Timing,
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::time::Duration;
+
+/// Measures one stage of a day, the input is parsed again for every iteration
+fn bench_stage<S>(
+    c: &mut Criterion,
+    day: &problems::Day,
+    input: &[u8],
+    name: &str,
+    parts: Parts,
+    stage: S,
+) where
+    S: Fn(&Timing) -> Option<Duration>,
+{
+    c.bench_function(&format!("day{}/{}", day.number, name), |b| {
+        b.iter_custom(|iterations| {
+            (0..iterations)
+                .map(|_| {
+                    let mut context = DayContext::from_bytes(input, parts);
+                    day.run(&mut context).expect("day failed");
+                    stage(context.timings()).expect("stage was not timed")
+                })
+                .sum()
+        })
+    });
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let cache = InputCache::from_env();
+    for day in problems::DAYS {
+        let input = match cache
+            .resolve(day.number, &InputKind::Default)
+            .and_then(|path| Ok(std::fs::read(path)?))
+        {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Skipping day{}: {}", day.number, e);
+                continue;
+            }
+        };
+        if let Err(e) = day.run(&mut DayContext::from_bytes(&input[..], Parts::Both)) {
+            eprintln!("Skipping day{}: {:#}", day.number, e);
+            continue;
+        }
+
+        bench_stage(c, day, &input, "parsing", Parts::One, |t| t.parsing);
+        bench_stage(c, day, &input, "part_1", Parts::One, |t| {
+            t.execution(Part::One)
+        });
+        bench_stage(c, day, &input, "part_2", Parts::Two, |t| {
+            t.execution(Part::Two)
+        });
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,141 @@
+use crate::{split_string_separator, Part};
+use color_eyre::{eyre::Context, Help, SectionExt};
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+/// Expected answers of an input, one `<part>: <answer>` per line
+#[derive(Debug, Default)]
+pub struct Answers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct Mismatch {
+    pub part: Part,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Mismatch {
+    pub fn diff(&self) -> String {
+        let mut diff = String::new();
+        for line in self.expected.lines() {
+            diff += &format!("- {}\n", line);
+        }
+        for line in self.actual.lines() {
+            diff += &format!("+ {}\n", line);
+        }
+        diff.pop();
+        diff
+    }
+
+    pub fn into_report(self) -> color_eyre::Report {
+        let diff = self.diff();
+        color_eyre::eyre::eyre!("{}", self).section(diff.header("Diff:"))
+    }
+
+    /// Report with the diff of each part, `mismatches` must not be empty
+    pub fn report_all(mut mismatches: Vec<Mismatch>) -> color_eyre::Report {
+        if mismatches.len() == 1 {
+            return mismatches.remove(0).into_report();
+        }
+
+        mismatches.iter().fold(
+            color_eyre::eyre::eyre!("Both parts do not match the expected answers"),
+            |report, mismatch| {
+                report.section(
+                    mismatch
+                        .diff()
+                        .header(format!("Diff of part {}:", mismatch.part)),
+                )
+            },
+        )
+    }
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Part {} does not match the expected answer", self.part)
+    }
+}
+
+impl Answers {
+    /// The answers of `inputs/dayN` are stored in `inputs/dayN.answers`
+    pub fn path(input: &Path) -> PathBuf {
+        let mut path = input.as_os_str().to_owned();
+        path.push(".answers");
+        path.into()
+    }
+
+    pub fn load(input: &Path) -> color_eyre::Result<Self> {
+        let path = Self::path(input);
+        let answers = std::fs::read_to_string(&path)
+            .with_context(|| format!("Could not read the answers: {:?}", path))?;
+        answers
+            .parse()
+            .with_context(|| format!("Malformed answers file: {:?}", path))
+    }
+
+    pub fn expected(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+
+    /// Parts without an expected answer are never reported
+    pub fn check(&self, part: Part, actual: &str) -> Option<Mismatch> {
+        match self.expected(part) {
+            Some(expected) if expected != actual => Some(Mismatch {
+                part,
+                expected: expected.to_owned(),
+                actual: actual.to_owned(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = color_eyre::eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            let (part, answer) = split_string_separator(line, ':')
+                .ok_or_else(|| color_eyre::eyre::eyre!("Missing ':' in line: {}", line))?;
+            let answer = Some(answer.trim().to_owned());
+            match part.trim().parse()? {
+                Part::One => answers.part1 = answer,
+                Part::Two => answers.part2 = answer,
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Answers;
+    use crate::Part;
+
+    #[test]
+    fn parse_and_check() {
+        let answers: Answers = "1: a,b: c\n\n2: 42\n".parse().unwrap();
+        assert_eq!(answers.expected(Part::One), Some("a,b: c"));
+        assert!(answers.check(Part::Two, "42").is_none());
+
+        let mismatch = answers.check(Part::Two, "41").unwrap();
+        assert_eq!(mismatch.diff(), "- 42\n+ 41");
+    }
+
+    #[test]
+    fn missing_part_is_unchecked() {
+        let answers: Answers = "2: 42".parse().unwrap();
+        assert!(answers.check(Part::One, "anything").is_none());
+    }
+}
@@ -0,0 +1,83 @@
+use aoc_2020::{inputs::CacheArgs, output::Format, problems, runner, Args, DayContext};
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+#[structopt(name = "aoc", about = "Advent of code 2020 solutions")]
+enum Command {
+    /// Run a single day on an input
+    Run {
+        #[structopt(short, long)]
+        day: u8,
+        #[structopt(flatten)]
+        args: Args,
+    },
+    /// Run both parts of every compiled day
+    All {
+        #[structopt(flatten)]
+        cache: CacheArgs,
+        /// Compare the answers against the ones stored in `dayN.answers`
+        #[structopt(long)]
+        check: bool,
+        #[structopt(long, default_value = "text", possible_values = &["text", "json", "csv"])]
+        format: Format,
+        /// Number of days run in parallel, defaults to the number of CPUs
+        #[structopt(short, long)]
+        jobs: Option<usize>,
+        /// Run the days one after the other, for accurate timings
+        #[structopt(long, conflicts_with = "jobs")]
+        serial: bool,
+    },
+    /// List the days that were compiled in
+    List,
+}
+
+fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
+
+    match Command::from_args() {
+        Command::Run { day, args } => {
+            let day = problems::get_day(day)?;
+            let mut context = DayContext::from_args(args, day.number)?;
+            day.run(&mut context)?;
+            context.repeat(day)?;
+            context.report(day.number)?;
+            context.check()?;
+        }
+        Command::All {
+            cache,
+            check,
+            format,
+            jobs,
+            serial,
+        } => {
+            let jobs = match (serial, jobs) {
+                (true, _) => 1,
+                (false, Some(jobs)) => jobs,
+                (false, None) => std::thread::available_parallelism().map_or(1, |n| n.get()),
+            };
+            let results = runner::run_all(&cache.cache(), &cache.kind(), check, jobs)?;
+            match format {
+                Format::Text => runner::print_summary(&results, check),
+                format => aoc_2020::output::emit(
+                    format,
+                    &results.iter().map(|r| r.record()).collect::<Vec<_>>(),
+                )?,
+            }
+
+            let failed = results.iter().filter(|result| result.is_failure()).count();
+            if failed != 0 {
+                color_eyre::eyre::bail!("{} parts failed", failed);
+            }
+        }
+        Command::List => {
+            if problems::DAYS.is_empty() {
+                println!("No day was compiled in, enable them with `--features dayN`");
+            }
+            for day in problems::DAYS {
+                println!("day{}", day.number);
+            }
+        }
+    }
+
+    Ok(())
+}
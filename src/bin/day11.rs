@@ -1,8 +0,0 @@
-use aoc_2020::{problems::day11::execute, DayContext};
-
-fn main() -> color_eyre::Result<()> {
-    let mut context = DayContext::load()?;
-    execute(&mut context)?;
-    context.report_timings();
-    Ok(())
-}
\ No newline at end of file
@@ -0,0 +1,124 @@
+use bstr::{BStr, ByteSlice};
+use color_eyre::{Section, SectionExt};
+use std::{fmt::Display, path::Path};
+
+/// Error of a line parser that knows where the problem starts in the line
+///
+/// The line helpers of [`DayContext`](crate::DayContext) use the column to place the caret
+/// under the snippet, the whole line is underlined otherwise
+#[derive(Debug)]
+pub struct ColumnError {
+    /// Byte offset in the line, starting at 0
+    pub column: usize,
+    pub message: String,
+}
+
+impl ColumnError {
+    pub fn new<M: Into<String>>(column: usize, message: M) -> Self {
+        Self {
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ColumnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (column {})", self.message, self.column + 1)
+    }
+}
+
+impl std::error::Error for ColumnError {}
+
+/// Wraps the error of a parser with the location of the line and a snippet of it
+///
+/// `line_number` starts at 1
+pub(crate) fn line_error(
+    error: color_eyre::Report,
+    path: Option<&Path>,
+    line_number: usize,
+    line: &BStr,
+) -> color_eyre::Report {
+    // A column given with `wrap_err` is only found by downcasting the report itself
+    let column = error
+        .downcast_ref::<ColumnError>()
+        .or_else(|| error.chain().find_map(|e| e.downcast_ref::<ColumnError>()))
+        .map(|e| e.column.min(line.len()));
+
+    let location = match (path, column) {
+        (Some(path), Some(column)) => format!("{}:{}:{}", path.display(), line_number, column + 1),
+        (Some(path), None) => format!("{}:{}", path.display(), line_number),
+        (None, Some(column)) => format!("line {}, column {}", line_number, column + 1),
+        (None, None) => format!("line {}", line_number),
+    };
+
+    error
+        .wrap_err(format!("Could not parse line {}", line_number))
+        .section(location.header("Input:"))
+        .section(snippet(line_number, line, column).header("Snippet:"))
+}
+
+/// Wraps the error of a group parser with the location of the group and its lines
+///
+/// `first_line` starts at 1
+pub(crate) fn group_error(
+    error: color_eyre::Report,
+    path: Option<&Path>,
+    first_line: usize,
+    lines: &[String],
+) -> color_eyre::Report {
+    let last_line = first_line + lines.len() - 1;
+    let location = match path {
+        Some(path) => format!("{}:{}", path.display(), first_line),
+        None => format!("line {}", first_line),
+    };
+
+    let gutter = last_line.to_string().len();
+    let snippet = lines
+        .iter()
+        .zip(first_line..)
+        .map(|(line, number)| format!("{:>width$} | {}", number, line, width = gutter))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    error
+        .wrap_err(format!(
+            "Could not parse the group at lines {}-{}",
+            first_line, last_line
+        ))
+        .section(location.header("Input:"))
+        .section(snippet.header("Snippet:"))
+}
+
+fn snippet(line_number: usize, line: &BStr, column: Option<usize>) -> String {
+    let (offset, width) = match column {
+        Some(column) => (line[..column].chars().count(), 1),
+        None => (0, line.chars().count().max(1)),
+    };
+    let gutter = " ".repeat(line_number.to_string().len());
+    format!(
+        "{} | {}\n{} | {}{}",
+        line_number,
+        line,
+        gutter,
+        " ".repeat(offset),
+        "^".repeat(width)
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{line_error, snippet, ColumnError};
+    use bstr::ByteSlice;
+
+    #[test]
+    fn caret() {
+        let line = b"1-3 # abc".as_bstr();
+        assert_eq!(snippet(12, line, Some(4)), "12 | 1-3 # abc\n   |     ^");
+        assert_eq!(snippet(7, line, None), "7 | 1-3 # abc\n  | ^^^^^^^^^");
+
+        let error = color_eyre::Report::new(ColumnError::new(4, "Unknown letter"));
+        let report = line_error(error, None, 12, line);
+        assert_eq!(report.to_string(), "Could not parse line 12");
+    }
+}
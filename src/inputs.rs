@@ -0,0 +1,138 @@
+use std::{fmt::Display, path::PathBuf};
+use structopt::StructOpt;
+
+/// Environment variable overriding the default `inputs` folder
+pub const INPUTS_VAR: &str = "AOC_INPUTS";
+
+/// Which of the inputs stored for a day to use
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputKind {
+    Default,
+    Example,
+    Named(String),
+}
+
+/// Folder storing the inputs:
+///
+/// - `dayN` is the input of day N
+/// - `dayN.example` is the example given in the puzzle
+/// - `<NAME>/dayN` is the input of another account
+#[derive(Clone, Debug)]
+pub struct InputCache {
+    root: PathBuf,
+}
+
+#[derive(Debug)]
+pub struct MissingInput {
+    pub day: u8,
+    pub path: PathBuf,
+}
+
+impl Display for MissingInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "There is no input for day {} at {:?}",
+            self.day, self.path
+        )
+    }
+}
+
+impl std::error::Error for MissingInput {}
+
+impl InputCache {
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Uses the folder in `AOC_INPUTS`, or `inputs`
+    pub fn from_env() -> Self {
+        Self::new(std::env::var_os(INPUTS_VAR).unwrap_or_else(|| "inputs".into()))
+    }
+
+    pub fn path(&self, day: u8, kind: &InputKind) -> PathBuf {
+        match kind {
+            InputKind::Default => self.root.join(format!("day{}", day)),
+            InputKind::Example => self.root.join(format!("day{}.example", day)),
+            InputKind::Named(name) => self.root.join(name).join(format!("day{}", day)),
+        }
+    }
+
+    /// Path of the input, which must exist and not be empty
+    ///
+    /// A missing input is reported as a [`MissingInput`] error
+    pub fn resolve(&self, day: u8, kind: &InputKind) -> color_eyre::Result<PathBuf> {
+        let path = self.path(day, kind);
+        let metadata = match std::fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(MissingInput { day, path }.into())
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        if !metadata.is_file() {
+            color_eyre::eyre::bail!("Input {:?} is not a file", path);
+        }
+        if metadata.len() == 0 {
+            color_eyre::eyre::bail!("Input {:?} is empty", path);
+        }
+
+        Ok(path)
+    }
+}
+
+#[derive(StructOpt)]
+pub struct CacheArgs {
+    /// Folder containing the inputs, named `dayN`
+    #[structopt(long, env = INPUTS_VAR, default_value = "inputs")]
+    inputs: PathBuf,
+    /// Use the input stored in `<INPUTS>/<NAME>/dayN`
+    #[structopt(long, conflicts_with = "example")]
+    name: Option<String>,
+    /// Use the example stored in `<INPUTS>/dayN.example`
+    #[structopt(long)]
+    example: bool,
+}
+
+impl CacheArgs {
+    pub fn cache(&self) -> InputCache {
+        InputCache::new(&self.inputs)
+    }
+
+    pub fn kind(&self) -> InputKind {
+        match (&self.name, self.example) {
+            (Some(name), _) => InputKind::Named(name.clone()),
+            (None, true) => InputKind::Example,
+            (None, false) => InputKind::Default,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{InputCache, InputKind, MissingInput};
+
+    #[test]
+    fn resolve() {
+        let root = std::env::temp_dir().join(format!("aoc_inputs_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("alice")).unwrap();
+        std::fs::write(root.join("day1"), "1721\n").unwrap();
+        std::fs::write(root.join("day1.example"), "").unwrap();
+        std::fs::write(root.join("alice").join("day1"), "979\n").unwrap();
+
+        let cache = InputCache::new(&root);
+        let alice = InputKind::Named("alice".into());
+        assert_eq!(
+            cache.resolve(1, &InputKind::Default).unwrap(),
+            root.join("day1")
+        );
+        assert_eq!(cache.resolve(1, &alice).unwrap(), root.join("alice/day1"));
+        assert!(cache.resolve(1, &InputKind::Example).is_err());
+
+        let missing = cache.resolve(2, &InputKind::Default).unwrap_err();
+        assert_eq!(missing.downcast_ref::<MissingInput>().unwrap().day, 2);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -3,14 +3,30 @@ use color_eyre::eyre::Context;
 use std::{
     fmt::Display,
     fs::File,
-    io::{BufRead, BufReader},
+    io::{BufRead, BufReader, Cursor, Read},
     path::PathBuf,
     time::Duration,
     time::Instant,
 };
 use structopt::StructOpt;
 
+pub mod answers;
+pub mod errors;
+pub mod grid;
+pub mod inputs;
+pub mod options;
+pub mod output;
 pub mod problems;
+pub mod runner;
+pub mod stats;
+
+use answers::{Answers, Mismatch};
+use errors::ColumnError;
+pub use grid::Grid;
+use inputs::CacheArgs;
+use options::DayOptions;
+use output::{Format, Record};
+use stats::Stats;
 
 pub fn split_string_separator(input: &str, separator: char) -> Option<(&str, &str)> {
     let separator_position = input.find(separator)?;
@@ -29,9 +45,56 @@ pub fn split_bytes_separator(input: &[u8], separator: u8) -> Option<(&[u8], &[u8
     Some((start, &end[1..]))
 }
 
-enum Part {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// The parts to run on a single parsing of the input
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parts {
     One,
     Two,
+    Both,
+}
+
+impl Parts {
+    pub fn contains(self, part: Part) -> bool {
+        matches!(
+            (self, part),
+            (Parts::Both, _) | (Parts::One, Part::One) | (Parts::Two, Part::Two)
+        )
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = Part> {
+        Part::ALL
+            .iter()
+            .copied()
+            .filter(move |&part| self.contains(part))
+    }
+}
+
+impl From<Part> for Parts {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => Parts::One,
+            Part::Two => Parts::Two,
+        }
+    }
 }
 
 #[inline]
@@ -41,27 +104,98 @@ pub fn open_input(day: u8, mut folder: PathBuf) -> color_eyre::Result<File> {
 }
 
 pub struct DayContext {
-    part: Part,
-    input_file: BufReader<File>,
+    parts: Parts,
+    input: Box<dyn BufRead>,
     timing: Timing,
+    answers: [Option<Answer>; 2],
+    expected: Option<Answers>,
+    format: Format,
     report: bool,
+    /// In-memory copy of the input, kept to repeat the runs
+    source: Option<Vec<u8>>,
+    repeat: usize,
+    samples: Vec<Timing>,
+    options: DayOptions,
+    /// Where the input comes from, to locate the parse errors
+    path: Option<PathBuf>,
+    /// Number of lines read so far
+    line: usize,
+    /// Whole input, filled by `input_bytes`
+    bytes: Vec<u8>,
+    /// When a parser started reading the input by itself, parsing ends with the first part
+    parse_start: Option<Instant>,
+}
+
+/// Result of a part: the bare answer, and optionally a sentence presenting it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    value: String,
+    description: Option<String>,
+}
+
+impl Answer {
+    pub fn new<T: Display>(value: T) -> Self {
+        Self {
+            value: value.to_string(),
+            description: None,
+        }
+    }
+
+    pub fn with_description<D: Into<String>>(mut self, description: D) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
 }
 
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.description {
+            Some(description) => write!(f, "{}", description),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
 pub struct Timing {
     pub parsing: Option<Duration>,
-    pub execution: Option<Duration>,
+    pub part_1: Option<Duration>,
+    pub part_2: Option<Duration>,
 }
 
 impl Timing {
     fn new() -> Self {
         Self {
             parsing: None,
-            execution: None,
+            part_1: None,
+            part_2: None,
+        }
+    }
+
+    pub fn execution(&self, part: Part) -> Option<Duration> {
+        match part {
+            Part::One => self.part_1,
+            Part::Two => self.part_2,
+        }
+    }
+
+    fn execution_mut(&mut self, part: Part) -> &mut Option<Duration> {
+        match part {
+            Part::One => &mut self.part_1,
+            Part::Two => &mut self.part_2,
         }
     }
 }
 
-fn format_time(dur: Duration) -> String {
+pub fn format_time(dur: Duration) -> String {
     let elapsed = dur.as_nanos() as f64;
 
     let secs_ns = (10.0f64).powi(9);
@@ -83,80 +217,309 @@ impl DayContext {
         &self.timing
     }
 
+    pub fn answer(&self, part: Part) -> Option<&Answer> {
+        self.answers[part as usize].as_ref()
+    }
+
+    /// Runs the day again on fresh contexts until `--repeat` runs were made
+    pub fn repeat(&mut self, day: &problems::Day) -> color_eyre::Result<()> {
+        let source = match &self.source {
+            Some(source) => source,
+            None => return Ok(()),
+        };
+
+        let mut samples = vec![self.timing];
+        for _ in 1..self.repeat {
+            let mut context = DayContext::from_bytes(source.clone(), self.parts);
+            context.path = self.path.clone();
+            context.options = self.options.clone();
+            day.run(&mut context)?;
+            samples.push(context.timing);
+        }
+        self.samples = samples;
+
+        Ok(())
+    }
+
+    /// With `--check`, fails with the diff of every part that does not match its expected answer
+    ///
+    /// Both parts run before the check, so that all the mismatches are reported at once
+    pub fn check(&self) -> color_eyre::Result<()> {
+        let expected = match &self.expected {
+            Some(expected) => expected,
+            None => return Ok(()),
+        };
+
+        let mismatches: Vec<_> = self
+            .parts
+            .iter()
+            .filter_map(|part| expected.check(part, self.answer(part)?.value()))
+            .collect();
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(Mismatch::report_all(mismatches))
+        }
+    }
+
+    /// Timings of every run when `--repeat` was given
+    pub fn samples(&self) -> &[Timing] {
+        &self.samples
+    }
+
+    /// Median of the repeated runs, or the timing of the single run
+    fn reported_timing(&self) -> Timing {
+        if self.samples.is_empty() {
+            return self.timing;
+        }
+
+        let median = |stats: Option<Stats>| stats.map(|s| s.median);
+        Timing {
+            parsing: median(Stats::parsing(&self.samples)),
+            part_1: median(Stats::execution(&self.samples, Part::One)),
+            part_2: median(Stats::execution(&self.samples, Part::Two)),
+        }
+    }
+
+    /// Reports the timings, or the whole result in the machine readable formats
+    pub fn report(&self, day: u8) -> color_eyre::Result<()> {
+        match self.format {
+            Format::Text => {
+                self.report_timings();
+                Ok(())
+            }
+            format => {
+                let timing = self.reported_timing();
+                let records: Vec<_> = self
+                    .parts
+                    .iter()
+                    .map(|part| {
+                        Record::new(
+                            day,
+                            part,
+                            self.answer(part).map(|a| a.value().to_owned()),
+                            Some(&timing),
+                        )
+                    })
+                    .collect();
+                output::emit(format, &records)
+            }
+        }
+    }
+
     pub fn report_timings(&self) {
+        if !self.samples.is_empty() {
+            self.report_stats();
+            return;
+        }
+
         println!("");
         println!("TIMINGS");
         if let Some(parsing) = self.timing.parsing {
             println!("  - Time taken for parsing: {}", format_time(parsing))
         }
 
-        if let Some(execution) = self.timing.execution {
-            println!("  - Time taken for execution: {}", format_time(execution))
+        for part in self.parts.iter() {
+            if let Some(execution) = self.timing.execution(part) {
+                match self.parts {
+                    Parts::Both => println!(
+                        "  - Time taken for part {}: {}",
+                        part,
+                        format_time(execution)
+                    ),
+                    _ => println!("  - Time taken for execution: {}", format_time(execution)),
+                }
+            }
+        }
+    }
+
+    fn report_stats(&self) {
+        println!();
+        println!("TIMINGS ({} runs)", self.samples.len());
+        if let Some(parsing) = Stats::parsing(&self.samples) {
+            println!("  - Parsing: {}", parsing)
+        }
+        for part in self.parts.iter() {
+            if let Some(execution) = Stats::execution(&self.samples, part) {
+                println!("  - Part {}: {}", part, execution)
+            }
         }
     }
 
-    pub fn load() -> color_eyre::Result<Self> {
-        let args = Args::from_args();
-        let input_file = File::open(&args.input)
-            .with_context(|| format!("Could not open input: {:?}", args.input))?;
-        let input_file = BufReader::new(input_file);
+    /// An input of `-` reads the puzzle from stdin, no input reads it from the cache
+    pub fn from_args(args: Args, day: u8) -> color_eyre::Result<Self> {
+        if args.repeat == 0 {
+            color_eyre::eyre::bail!("--repeat needs at least one run");
+        }
+
+        let path = match args.input {
+            Some(path) => path,
+            None => args.cache.cache().resolve(day, &args.cache.kind())?,
+        };
+        let stdin = path.as_os_str() == "-";
+        let mut input: Box<dyn BufRead> = if stdin {
+            Box::new(BufReader::new(std::io::stdin()))
+        } else {
+            let input_file =
+                File::open(&path).with_context(|| format!("Could not open input: {:?}", path))?;
+            Box::new(BufReader::new(input_file))
+        };
+        let expected = match (args.check, stdin) {
+            (false, _) => None,
+            (true, false) => Some(Answers::load(&path)?),
+            (true, true) => color_eyre::eyre::bail!("Can't check the answers of stdin"),
+        };
+
+        let source = if args.repeat > 1 {
+            let mut source = Vec::new();
+            input
+                .read_to_end(&mut source)
+                .context("Could not read the input")?;
+            input = Box::new(Cursor::new(source.clone()));
+            Some(source)
+        } else {
+            None
+        };
 
         Ok(Self {
             timing: Timing::new(),
-            input_file,
-            part: args.part,
-            report: true,
+            answers: [None, None],
+            expected,
+            input,
+            parts: args.part,
+            format: args.format,
+            report: args.format == Format::Text,
+            source,
+            repeat: args.repeat,
+            options: args.options,
+            samples: Vec::new(),
+            path: if stdin { None } else { Some(path) },
+            line: 0,
+            bytes: Vec::new(),
+            parse_start: None,
         })
     }
 
-    fn new(file: File, part: Part) -> Self {
+    pub fn from_reader<R: BufRead + 'static, P: Into<Parts>>(input: R, parts: P) -> Self {
         Self {
-            input_file: BufReader::new(file),
+            input: Box::new(input),
             timing: Timing::new(),
-            part,
+            answers: [None, None],
+            expected: None,
+            parts: parts.into(),
+            format: Format::Text,
             report: false,
+            source: None,
+            repeat: 1,
+            options: DayOptions::default(),
+            samples: Vec::new(),
+            path: None,
+            line: 0,
+            bytes: Vec::new(),
+            parse_start: None,
         }
     }
 
+    pub fn with_options(mut self, options: DayOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn options(&self) -> &DayOptions {
+        &self.options
+    }
+
+    /// Sets the path reported in the parse errors
+    pub fn with_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Builds a context over an in-memory input, such as a `&str` or a `&[u8]`
+    pub fn from_bytes<B: Into<Vec<u8>>, P: Into<Parts>>(input: B, parts: P) -> Self {
+        Self::from_reader(Cursor::new(input.into()), parts)
+    }
+
     #[inline]
-    pub fn new_part1(file: File) -> Self {
-        Self::new(file, Part::One)
+    pub fn new_part1<R: Read + 'static>(input: R) -> Self {
+        Self::from_reader(BufReader::new(input), Part::One)
     }
     #[inline]
-    pub fn new_part2(file: File) -> Self {
-        Self::new(file, Part::Two)
+    pub fn new_part2<R: Read + 'static>(input: R) -> Self {
+        Self::from_reader(BufReader::new(input), Part::Two)
     }
 
-    pub fn execute<I, R, P1, P2>(
-        &mut self,
-        input: I,
-        part1: P1,
-        part2: P2,
-    ) -> color_eyre::Result<()>
+    /// Runs the selected parts, the input is cloned when both of them need it
+    pub fn execute<I, P1, P2>(&mut self, input: I, part1: P1, part2: P2) -> color_eyre::Result<()>
+    where
+        I: Clone,
+        P1: FnOnce(I) -> color_eyre::Result<Answer>,
+        P2: FnOnce(I) -> color_eyre::Result<Answer>,
+    {
+        if let (None, Some(start)) = (self.timing.parsing, self.parse_start) {
+            self.timing.parsing = Some(start.elapsed());
+        }
+
+        match self.parts {
+            Parts::One => self.run_part(Part::One, input, part1),
+            Parts::Two => self.run_part(Part::Two, input, part2),
+            Parts::Both => {
+                self.run_part(Part::One, input.clone(), part1)?;
+                self.run_part(Part::Two, input, part2)
+            }
+        }
+    }
+
+    fn run_part<I, P>(&mut self, part: Part, input: I, solver: P) -> color_eyre::Result<()>
     where
-        R: Display,
-        P1: FnOnce(I) -> color_eyre::Result<R>,
-        P2: FnOnce(I) -> color_eyre::Result<R>,
+        P: FnOnce(I) -> color_eyre::Result<Answer>,
     {
         let start = Instant::now();
-        let res = match self.part {
-            Part::One => part1(input)?,
-            Part::Two => part2(input)?,
-        };
-        if self.timing.execution.is_none() {
-            self.timing.execution = Some(start.elapsed());
+        let res = solver(input)?;
+        let execution = self.timing.execution_mut(part);
+        if execution.is_none() {
+            *execution = Some(start.elapsed());
         }
 
         if self.report {
-            println!("{}", res);
+            match self.parts {
+                Parts::Both => println!("Part {}: {}", part, res),
+                _ => println!("{}", res),
+            }
         }
 
+        self.answers[part as usize] = Some(res);
+
         Ok(())
     }
 
+    fn line_error(&self, error: color_eyre::Report, line: &BStr) -> color_eyre::Report {
+        errors::line_error(error, self.path.as_deref(), self.line, line)
+    }
+
+    /// Reads the rest of the input at once, to be split without copies
+    ///
+    /// The parsing time runs until the first part is executed
+    pub fn input_bytes(&mut self) -> color_eyre::Result<&[u8]> {
+        self.parse_start.get_or_insert_with(Instant::now);
+        self.input
+            .read_to_end(&mut self.bytes)
+            .context("Could not read the input")?;
+        Ok(&self.bytes)
+    }
+
+    /// Like [`input_bytes`](DayContext::input_bytes) but gives away the buffer, for parsers
+    /// whose input borrows from it
+    pub fn take_input_bytes(&mut self) -> color_eyre::Result<Vec<u8>> {
+        self.input_bytes()?;
+        Ok(std::mem::take(&mut self.bytes))
+    }
+
     pub fn read_line(&mut self) -> color_eyre::Result<String> {
+        self.parse_start.get_or_insert_with(Instant::now);
         let mut s = String::new();
-        self.input_file.read_line(&mut s)?;
+        self.input.read_line(&mut s)?;
+        self.line += 1;
         if s.ends_with('\n') {
             s.pop();
             if s.ends_with('\r') {
@@ -177,12 +540,13 @@ impl DayContext {
         loop {
             buf.clear();
             match self
-                .input_file
+                .input
                 .read_line(&mut buf)
                 .with_context(|| "Could not read line in the input file")?
             {
                 0 => break,
                 _ => {
+                    self.line += 1;
                     if buf.ends_with('\n') {
                         buf.pop();
                         if buf.ends_with('\r') {
@@ -190,8 +554,7 @@ impl DayContext {
                         }
                     }
                     result.push(
-                        parser(&buf)
-                            .with_context(|| format!("Could not parse the line {}", buf))?,
+                        parser(&buf).map_err(|e| self.line_error(e, buf.as_bytes().as_bstr()))?,
                     );
                 }
             }
@@ -213,19 +576,20 @@ impl DayContext {
         for i in 0.. {
             buf.clear();
             match self
-                .input_file
+                .input
                 .read_line(&mut buf)
                 .with_context(|| "Could not read line in the input file")?
             {
                 0 => break,
                 _ => {
+                    self.line += 1;
                     if buf.ends_with('\n') {
                         buf.pop();
                         if buf.ends_with('\r') {
                             buf.pop();
                         }
                     }
-                    parser(i, &buf).with_context(|| format!("Could not parse line: {}", buf))?
+                    parser(i, &buf).map_err(|e| self.line_error(e, buf.as_bytes().as_bstr()))?
                 }
             }
         }
@@ -237,6 +601,118 @@ impl DayContext {
         Ok(())
     }
 
+    /// Parses each block of lines separated by blank lines
+    pub fn parse_groups<I, F: FnMut(&[String]) -> color_eyre::Result<I>>(
+        &mut self,
+        mut parser: F,
+    ) -> color_eyre::Result<Vec<I>> {
+        let mut result = Vec::new();
+        self.accumulate_groups(|_, group| {
+            result.push(parser(group)?);
+            Ok(())
+        })?;
+
+        Ok(result)
+    }
+
+    /// Calls the parser on each block of lines separated by blank lines, with its index
+    ///
+    /// Consecutive blank lines don't create empty groups
+    pub fn accumulate_groups<F: FnMut(usize, &[String]) -> color_eyre::Result<()>>(
+        &mut self,
+        mut parser: F,
+    ) -> color_eyre::Result<()> {
+        let mut group = Vec::new();
+        let mut index = 0;
+        let mut buf = String::new();
+        let start = Instant::now();
+        loop {
+            buf.clear();
+            let read = self
+                .input
+                .read_line(&mut buf)
+                .with_context(|| "Could not read line in the input file")?;
+            if read != 0 {
+                self.line += 1;
+            }
+
+            let line = buf.trim_end_matches(&['\n', '\r'][..]);
+            if !line.is_empty() {
+                group.push(line.to_owned());
+            } else if !group.is_empty() {
+                // The blank line is not part of the group
+                let first_line = self.line - group.len() - (read != 0) as usize + 1;
+                parser(index, &group).map_err(|e| {
+                    errors::group_error(e, self.path.as_deref(), first_line, &group)
+                })?;
+                group.clear();
+                index += 1;
+            }
+
+            if read == 0 {
+                break;
+            }
+        }
+
+        if self.timing.parsing.is_none() {
+            self.timing.parsing = Some(start.elapsed());
+        }
+
+        Ok(())
+    }
+
+    /// Parses each byte of the lines as a cell of a grid, all the lines having the same length
+    pub fn parse_grid<T, F: FnMut(u8) -> color_eyre::Result<T>>(
+        &mut self,
+        mut parser: F,
+    ) -> color_eyre::Result<Grid<T>> {
+        let mut width = None;
+        let rows = self.parse_byte_lines(|line| {
+            let width = *width.get_or_insert(line.len());
+            if line.len() != width {
+                return Err(ColumnError::new(
+                    width.min(line.len()),
+                    format!("Expected {} cells, got {}", width, line.len()),
+                )
+                .into());
+            }
+
+            line.iter()
+                .enumerate()
+                .map(|(column, &cell)| {
+                    parser(cell).map_err(|e| {
+                        e.wrap_err(ColumnError::new(
+                            column,
+                            format!("Invalid cell {:?}", cell as char),
+                        ))
+                    })
+                })
+                .collect()
+        })?;
+
+        Grid::from_rows(rows)
+    }
+
+    /// Parses each line of `input`, the parsed items can borrow from it instead of allocating
+    ///
+    /// `input` is usually the buffer given by [`take_input_bytes`](DayContext::take_input_bytes)
+    pub fn parse_borrowed_lines<'b, I, F: FnMut(&'b BStr) -> color_eyre::Result<I>>(
+        &mut self,
+        input: &'b [u8],
+        mut parser: F,
+    ) -> color_eyre::Result<Vec<I>> {
+        self.parse_start.get_or_insert_with(Instant::now);
+
+        let mut result = Vec::new();
+        for line in ByteSlice::lines(input) {
+            self.line += 1;
+            let line = line.as_bstr();
+            result.push(parser(line).map_err(|e| self.line_error(e, line))?);
+        }
+
+        Ok(result)
+    }
+
     pub fn parse_byte_lines<I, F: FnMut(&BStr) -> color_eyre::Result<I>>(
         &mut self,
         mut parser: F,
@@ -248,12 +724,13 @@ impl DayContext {
         loop {
             buf.clear();
             match self
-                .input_file
+                .input
                 .read_until(b'\n', &mut buf)
                 .with_context(|| "Could not read line in the input file")?
             {
                 0 => break,
                 _ => {
+                    self.line += 1;
                     if buf.ends_with(&[b'\n']) {
                         buf.pop();
                         if buf.ends_with(&[b'\r']) {
@@ -261,7 +738,7 @@ impl DayContext {
                         }
                     }
                     result.push(
-                        parser(buf.as_bstr()).with_context(|| format!("Could not parse line"))?,
+                        parser(buf.as_bstr()).map_err(|e| self.line_error(e, buf.as_bstr()))?,
                     );
                 }
             }
@@ -283,20 +760,20 @@ impl DayContext {
         for i in 0.. {
             buf.clear();
             match self
-                .input_file
+                .input
                 .read_until(b'\n', &mut buf)
                 .with_context(|| "Could not read line in the input file")?
             {
                 0 => break,
                 _ => {
+                    self.line += 1;
                     if buf.ends_with(&[b'\n']) {
                         buf.pop();
                         if buf.ends_with(&[b'\r']) {
                             buf.pop();
                         }
                     }
-                    parser(i, buf.as_bstr())
-                        .with_context(|| format!("Could not parse line: {}", buf.as_bstr()))?
+                    parser(i, buf.as_bstr()).map_err(|e| self.line_error(e, buf.as_bstr()))?
                 }
             }
         }
@@ -310,11 +787,24 @@ impl DayContext {
 }
 
 #[derive(StructOpt)]
-struct Args {
-    #[structopt(short, long, default_value = "1", possible_values = &["1", "2"])]
-    part: Part,
-    #[structopt(short, long)]
-    input: PathBuf,
+pub struct Args {
+    #[structopt(short, long, default_value = "both", possible_values = &["1", "2", "both"])]
+    part: Parts,
+    /// Puzzle input, `-` reads it from stdin. Defaults to the input of the day in `--inputs`
+    #[structopt(short, long, conflicts_with_all = &["name", "example"])]
+    input: Option<PathBuf>,
+    #[structopt(flatten)]
+    cache: CacheArgs,
+    /// Compare the answer against the one stored in `<INPUT>.answers`
+    #[structopt(long)]
+    check: bool,
+    #[structopt(long, default_value = "text", possible_values = &["text", "json", "csv"])]
+    format: Format,
+    /// Runs parsing and execution N times and reports statistics on their timings
+    #[structopt(long, default_value = "1")]
+    repeat: usize,
+    #[structopt(flatten)]
+    options: DayOptions,
 }
 
 impl std::str::FromStr for Part {
@@ -328,3 +818,141 @@ impl std::str::FromStr for Part {
         }
     }
 }
+
+impl std::str::FromStr for Parts {
+    type Err = color_eyre::eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "both" => Ok(Self::Both),
+            part => Ok(part.parse::<Part>()?.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Answer, DayContext, Part, Parts};
+    use bstr::ByteSlice;
+
+    #[test]
+    fn in_memory_input() {
+        let mut context = DayContext::from_bytes("1\n2\r\n3", Part::One);
+        let lines = context
+            .parse_lines(|line| Ok(line.parse::<u64>()?))
+            .unwrap();
+        assert_eq!(lines, vec![1, 2, 3]);
+
+        let mut context = DayContext::from_bytes(&b"ab\ncd\n"[..], Part::Two);
+        let lines = context.parse_byte_lines(|line| Ok(line.len())).unwrap();
+        assert_eq!(lines, vec![2, 2]);
+    }
+
+    #[test]
+    fn parse_error_line() {
+        let mut context = DayContext::from_bytes("1\n2\nx\n4", Part::One);
+        let error = context
+            .parse_lines(|line| Ok(line.parse::<u64>()?))
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Could not parse line 3");
+    }
+
+    #[test]
+    fn groups() {
+        let mut context = DayContext::from_bytes("a\nb\n\n\nc\r\n\nd\ne\nf", Part::One);
+        let groups = context.parse_groups(|group| Ok(group.join(""))).unwrap();
+        assert_eq!(groups, vec!["ab", "c", "def"]);
+
+        let mut context = DayContext::from_bytes("1\n\n2\nx\n", Part::One);
+        let error = context
+            .parse_groups(|group| {
+                group
+                    .iter()
+                    .map(|line| Ok(line.parse::<u64>()?))
+                    .collect::<color_eyre::Result<Vec<_>>>()
+            })
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Could not parse the group at lines 3-4");
+    }
+
+    #[test]
+    fn grid() {
+        let mut context = DayContext::from_bytes("#.\n.#\n", Part::One);
+        let grid = context.parse_grid(|cell| Ok(cell == b'#')).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert!(grid[(1, 1)] && !grid[(1, 0)]);
+
+        let mut context = DayContext::from_bytes("#.\n.#.\n", Part::One);
+        assert!(context.parse_grid(|cell| Ok(cell == b'#')).is_err());
+    }
+
+    #[test]
+    fn whole_input() {
+        let mut context = DayContext::from_bytes("2\n3\n", Part::One);
+        let sum = context
+            .input_bytes()
+            .unwrap()
+            .lines()
+            .map(|line| line.to_str().unwrap().parse::<u64>().unwrap())
+            .sum::<u64>();
+        assert_eq!(sum, 5);
+        // The input was already read, the buffer stays the same
+        assert_eq!(context.input_bytes().unwrap(), b"2\n3\n");
+
+        context
+            .execute(sum, |sum| Ok(Answer::new(sum)), |_| unreachable!())
+            .unwrap();
+        assert!(context.timings().parsing.is_some());
+    }
+
+    #[test]
+    fn borrowed_lines() {
+        let mut context = DayContext::from_bytes("ab cd\nef gh\n", Part::One);
+        let input = context.take_input_bytes().unwrap();
+        let words = context
+            .parse_borrowed_lines(&input, |line| Ok(line.split_str(" ").next()))
+            .unwrap();
+        assert_eq!(words, vec![Some(&b"ab"[..]), Some(&b"ef"[..])]);
+    }
+
+    #[test]
+    fn both_parts() {
+        let mut context = DayContext::from_bytes("2\n3\n4", Parts::Both);
+        let lines = context
+            .parse_lines(|line| Ok(line.parse::<u64>()?))
+            .unwrap();
+        context
+            .execute(
+                lines,
+                |l| Ok(Answer::new(l.iter().sum::<u64>())),
+                |l| Ok(Answer::new(l.iter().product::<u64>())),
+            )
+            .unwrap();
+
+        assert_eq!(context.answer(Part::One).unwrap().value(), "9");
+        assert_eq!(context.answer(Part::Two).unwrap().value(), "24");
+        assert!(context.timings().part_1.is_some());
+        assert!(context.timings().part_2.is_some());
+    }
+
+    #[test]
+    fn check_after_both_parts() {
+        let mut context = DayContext::from_bytes("", Parts::Both);
+        context.expected = Some("1: 1\n2: 2\n".parse().unwrap());
+        context
+            .execute((), |_| Ok(Answer::new(3)), |_| Ok(Answer::new(4)))
+            .unwrap();
+
+        assert_eq!(context.answer(Part::Two).unwrap().value(), "4");
+        assert_eq!(
+            context.check().unwrap_err().to_string(),
+            "Both parts do not match the expected answers"
+        );
+
+        context.answers[Part::One as usize] = Some(Answer::new(1));
+        assert_eq!(
+            context.check().unwrap_err().to_string(),
+            "Part 2 does not match the expected answer"
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Options changing the puzzle solved by some days, ignored by the others
+#[derive(StructOpt, Clone, Debug, Default)]
+pub struct DayOptions {
+    /// Day 1: value the entries must sum to, instead of 2020
+    #[structopt(long)]
+    pub target: Option<u64>,
+    /// Day 1: number of entries summing to the target, instead of 2 for part 1 and 3 for part 2
+    #[structopt(long)]
+    pub terms: Option<usize>,
+    /// Day 2: policy applied to both parts, as clauses separated by `;` (`count`, `positions`,
+    /// `distinct >= N`, `length <= N`, `matches REGEX`)
+    #[structopt(long)]
+    pub policy: Option<String>,
+    /// Days 2 and 4: explain why each invalid password or passport was refused
+    #[structopt(long)]
+    pub explain: bool,
+    /// Day 3: slopes followed by both parts, as `RIGHT,DOWN` where both can be fractions
+    #[structopt(long)]
+    pub slopes: Vec<String>,
+    /// Day 3: draw the map with `O` on the path and `X` on the trees hit
+    #[structopt(long)]
+    pub render: bool,
+    /// Day 4: file describing the passport fields, replacing the rules of the puzzle
+    #[structopt(long)]
+    pub schema: Option<PathBuf>,
+    /// Day 5: letters of the boarding passes, as `<LOWER><UPPER><WIDTH>` for the row and the
+    /// column, `FB7,LR3` for the puzzle
+    #[structopt(long)]
+    pub layout: Option<String>,
+    /// Day 5: list every missing seat with both neighbours present, instead of the first one
+    #[structopt(long)]
+    pub all_gaps: bool,
+}
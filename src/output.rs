@@ -0,0 +1,190 @@
+use crate::{Part, Timing};
+use std::io::Write;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl std::str::FromStr for Format {
+    type Err = color_eyre::eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => color_eyre::eyre::bail!("Invalid output format: {}", s),
+        }
+    }
+}
+
+/// Machine readable result of a part, durations are in nanoseconds
+///
+/// `wall_ns` is the wall-clock time of the whole day, so both parts of a day share it
+#[derive(Debug)]
+pub struct Record {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Option<String>,
+    pub parsing_ns: Option<u128>,
+    pub execution_ns: Option<u128>,
+    pub wall_ns: Option<u128>,
+    pub error: Option<String>,
+}
+
+impl Record {
+    pub fn new(day: u8, part: Part, answer: Option<String>, timing: Option<&Timing>) -> Self {
+        Self {
+            day,
+            part,
+            answer,
+            parsing_ns: timing.and_then(|t| t.parsing).map(|d| d.as_nanos()),
+            execution_ns: timing.and_then(|t| t.execution(part)).map(|d| d.as_nanos()),
+            wall_ns: None,
+            error: None,
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+fn or_null<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "null".into(), |v| v.to_string())
+}
+
+pub fn write_json<W: Write>(out: &mut W, records: &[Record]) -> std::io::Result<()> {
+    writeln!(out, "[")?;
+    for (i, record) in records.iter().enumerate() {
+        write!(
+            out,
+            "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"parsing_ns\": {}, \"execution_ns\": {}, \"wall_ns\": {}",
+            record.day,
+            record.part,
+            or_null(record.answer.as_deref().map(json_string)),
+            or_null(record.parsing_ns),
+            or_null(record.execution_ns),
+            or_null(record.wall_ns),
+        )?;
+        if let Some(error) = &record.error {
+            write!(out, ", \"error\": {}", json_string(error))?;
+        }
+        let separator = if i + 1 == records.len() { "" } else { "," };
+        writeln!(out, "}}{}", separator)?;
+    }
+    writeln!(out, "]")
+}
+
+pub fn write_csv<W: Write>(out: &mut W, records: &[Record]) -> std::io::Result<()> {
+    writeln!(out, "day,part,answer,parsing_ns,execution_ns,wall_ns,error")?;
+    for record in records {
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{}",
+            record.day,
+            record.part,
+            record.answer.as_deref().map(csv_field).unwrap_or_default(),
+            record.parsing_ns.map(|t| t.to_string()).unwrap_or_default(),
+            record
+                .execution_ns
+                .map(|t| t.to_string())
+                .unwrap_or_default(),
+            record.wall_ns.map(|t| t.to_string()).unwrap_or_default(),
+            record.error.as_deref().map(csv_field).unwrap_or_default(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the records to stdout, the text format is handled by the callers
+pub fn emit(format: Format, records: &[Record]) -> color_eyre::Result<()> {
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    match format {
+        Format::Text => (),
+        Format::Json => write_json(&mut out, records)?,
+        Format::Csv => write_csv(&mut out, records)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{write_csv, write_json, Record};
+    use crate::Part;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                day: 1,
+                part: Part::One,
+                answer: Some("Found \"a\", b".into()),
+                parsing_ns: Some(12),
+                execution_ns: Some(34),
+                wall_ns: Some(56),
+                error: None,
+            },
+            Record {
+                day: 1,
+                part: Part::Two,
+                answer: None,
+                parsing_ns: None,
+                execution_ns: None,
+                wall_ns: None,
+                error: Some("missing input".into()),
+            },
+        ]
+    }
+
+    #[test]
+    fn json() {
+        let mut out = Vec::new();
+        write_json(&mut out, &records()).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            r#"[
+  {"day": 1, "part": 1, "answer": "Found \"a\", b", "parsing_ns": 12, "execution_ns": 34, "wall_ns": 56},
+  {"day": 1, "part": 2, "answer": null, "parsing_ns": null, "execution_ns": null, "wall_ns": null, "error": "missing input"}
+]
+"#
+        );
+    }
+
+    #[test]
+    fn csv() {
+        let mut out = Vec::new();
+        write_csv(&mut out, &records()).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "day,part,answer,parsing_ns,execution_ns,wall_ns,error\n\
+             1,1,\"Found \"\"a\"\", b\",12,34,56,\n\
+             1,2,,,,,missing input\n"
+        );
+    }
+}
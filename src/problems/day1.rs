@@ -1,56 +1,133 @@
-use crate::DayContext;
-
-pub fn part_1(lines: &[u64]) -> color_eyre::Result<String> {
-    let mut expense = &lines[..];
-    while expense.len() > 2 {
-        let rest = &expense[1..];
-        let value = expense[0];
-        for other in rest {
-            if other + value == 2020 {
-                return Ok(format!(
-                    "Found {} + {} = 2020; result is {}",
-                    value,
-                    other,
-                    other * value
-                ));
+use crate::{Answer, DayContext};
+use bstr::ByteSlice;
+use std::{cmp::Ordering, collections::HashSet};
+
+const TARGET: u64 = 2020;
+
+/// Finds `k` of the values summing to `target`
+///
+/// Pairs are found with a hash set, more terms with a sorted copy of the values and two pointers
+pub fn find_k_sum(values: &[u64], k: usize, target: u64) -> Option<Vec<u64>> {
+    match k {
+        0 if target == 0 => Some(Vec::new()),
+        0 => None,
+        1 => values.iter().find(|&&v| v == target).map(|&v| vec![v]),
+        2 => {
+            let mut seen = HashSet::new();
+            for &value in values {
+                if let Some(complement) = target.checked_sub(value) {
+                    if seen.contains(&complement) {
+                        return Some(vec![complement, value]);
+                    }
+                }
+                seen.insert(value);
             }
+            None
+        }
+        _ => {
+            let mut sorted = values.to_vec();
+            sorted.sort_unstable();
+            sorted_k_sum(&sorted, k, target)
         }
-        expense = rest;
     }
-    color_eyre::eyre::bail!("Did not find an answer")
-}
-
-pub fn part_2(lines: &[u64]) -> color_eyre::Result<String> {
-    let mut expense = &lines[..];
-    while expense.len() > 3 {
-        let value = expense[0];
-
-        let seconds = &expense[1..];
-        let thirds = &expense[2..];
-        for second in seconds {
-            for third in thirds {
-                if value + second + third == 2020 {
-                    return Ok(format!(
-                        "Found {} + {} + {} = 2020; result is {}",
-                        value,
-                        second,
-                        third,
-                        value * second * third
-                    ));
-                }
+}
+
+/// `values` needs to be sorted and `k` to be at least 2
+fn sorted_k_sum(values: &[u64], k: usize, target: u64) -> Option<Vec<u64>> {
+    if k == 2 {
+        let (mut low, mut high) = (0, values.len().checked_sub(1)?);
+        while low < high {
+            let sum = values[low].checked_add(values[high]);
+            match sum.map_or(Ordering::Greater, |sum| sum.cmp(&target)) {
+                Ordering::Less => low += 1,
+                Ordering::Greater => high -= 1,
+                Ordering::Equal => return Some(vec![values[low], values[high]]),
             }
         }
-        expense = seconds;
+        return None;
     }
-    color_eyre::eyre::bail!("Did not find an answer")
+
+    for (i, &first) in values.iter().enumerate() {
+        // The other terms are at least as large as the first one
+        if first.saturating_mul(k as u64) > target {
+            break;
+        }
+        if i > 0 && values[i - 1] == first {
+            continue;
+        }
+        if let Some(mut terms) = sorted_k_sum(&values[i + 1..], k - 1, target - first) {
+            terms.insert(0, first);
+            return Some(terms);
+        }
+    }
+
+    None
 }
 
-pub fn parsing(ctx: &mut DayContext) -> color_eyre::Result<Vec<u64>> {
-    let lines = ctx.parse_lines(|x| -> Result<u64, _> { Ok(x.parse()?) })?;
-    Ok(lines)
+fn k_sum_answer(values: &[u64], k: usize, target: u64) -> color_eyre::Result<Answer> {
+    let terms = find_k_sum(values, k, target).ok_or_else(|| {
+        color_eyre::eyre::eyre!("Did not find {} entries summing to {}", k, target)
+    })?;
+    let result = terms
+        .iter()
+        .try_fold(1u64, |product, &term| product.checked_mul(term))
+        .ok_or_else(|| color_eyre::eyre::eyre!("The product of {:?} overflows", terms))?;
+
+    let sum = terms
+        .iter()
+        .map(u64::to_string)
+        .collect::<Vec<_>>()
+        .join(" + ");
+    Ok(Answer::new(result)
+        .with_description(format!("Found {} = {}; result is {}", sum, target, result)))
+}
+
+pub fn part_1(lines: &[u64]) -> color_eyre::Result<Answer> {
+    k_sum_answer(lines, 2, TARGET)
+}
+
+pub fn part_2(lines: &[u64]) -> color_eyre::Result<Answer> {
+    k_sum_answer(lines, 3, TARGET)
+}
+
+pub fn parsing(ctx: &mut DayContext, input: &[u8]) -> color_eyre::Result<Vec<u64>> {
+    ctx.parse_borrowed_lines(input, |line| Ok(line.to_str()?.parse()?))
 }
 
 pub fn execute(context: &mut DayContext) -> color_eyre::Result<()> {
-    let lines = parsing(context)?;
-    context.execute(lines.as_ref(), part_1, part_2)
+    let input = context.take_input_bytes()?;
+    let lines = parsing(context, &input)?;
+
+    let target = context.options().target.unwrap_or(TARGET);
+    let terms = context.options().terms;
+    if terms == Some(0) {
+        color_eyre::eyre::bail!("--terms needs at least one term");
+    }
+    context.execute(
+        lines.as_ref(),
+        |lines| k_sum_answer(lines, terms.unwrap_or(2), target),
+        |lines| k_sum_answer(lines, terms.unwrap_or(3), target),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::find_k_sum;
+
+    const EXPENSES: [u64; 6] = [1721, 979, 366, 299, 675, 1456];
+
+    #[test]
+    fn k_sums() {
+        let mut pair = find_k_sum(&EXPENSES, 2, 2020).unwrap();
+        pair.sort_unstable();
+        assert_eq!(pair, vec![299, 1721]);
+        assert_eq!(find_k_sum(&EXPENSES, 3, 2020).unwrap(), vec![366, 675, 979]);
+        assert_eq!(
+            find_k_sum(&EXPENSES, 4, 299 + 366 + 675 + 1456),
+            Some(vec![299, 366, 675, 1456])
+        );
+        assert_eq!(find_k_sum(&EXPENSES, 1, 979), Some(vec![979]));
+        assert_eq!(find_k_sum(&EXPENSES, 3, 10), None);
+        assert_eq!(find_k_sum(&[], 3, 10), None);
+    }
 }
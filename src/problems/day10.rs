@@ -1,37 +1,45 @@
 use std::collections::HashMap;
 use nalgebra::DMatrix;
 
-use crate::DayContext;
-
-type Input<'i> = &'i mut [u64];
-
-fn differences(input: &[u64]) -> (u64, u64) {
-    let (one, two, three) = match input[0] {
-        1 => (1, 0, 0),
-        2 => (0, 1, 0),
-        3 => (0, 0, 1),
-        _ => panic!("Invalid jolt difference"),
+use crate::{Answer, DayContext};
+
+type Input<'i> = &'i [u64];
+
+fn differences(input: &[u64]) -> color_eyre::Result<(u64, u64)> {
+    let (one, two, three) = match input.first() {
+        Some(1) => (1, 0, 0),
+        Some(2) => (0, 1, 0),
+        Some(3) => (0, 0, 1),
+        Some(first) => color_eyre::eyre::bail!(
+            "Invalid jolt difference between the outlet and the adapter {}",
+            first
+        ),
+        None => color_eyre::eyre::bail!("There are no adapters"),
     };
-    let (one, _, three) = input.iter().zip(input.iter().skip(1)).fold(
+    let (one, _, three) = input.iter().zip(input.iter().skip(1)).try_fold(
         (one, two, three + 1),
         |(one, two, three), (&current, &next)| match next - current {
-            1 => (one + 1, two, three),
-            2 => (one, two + 1, three),
-            3 => (one, two, three + 1),
-            _ => panic!("Invalid jolt difference"),
+            1 => Ok((one + 1, two, three)),
+            2 => Ok((one, two + 1, three)),
+            3 => Ok((one, two, three + 1)),
+            _ => Err(color_eyre::eyre::eyre!(
+                "Invalid jolt difference between the adapters {} and {}",
+                current,
+                next
+            )),
         },
-    );
+    )?;
 
-    (one, three)
+    Ok((one, three))
 }
 
-pub fn part_1(input: Input) -> color_eyre::Result<String> {
-    input.sort();
-    let (one, three) = differences(input);
-    Ok(format!("One * three jolt: {}", one * three))
+pub fn part_1(input: Input) -> color_eyre::Result<Answer> {
+    let (one, three) = differences(input)?;
+    let result = one * three;
+    Ok(Answer::new(result).with_description(format!("One * three jolt: {}", result)))
 }
 
-fn chain_count(adaptaters: &[u64]) -> u64 {
+fn chain_count(adaptaters: &[u64]) -> color_eyre::Result<u64> {
     let mut matrix: DMatrix<u64> =
         nalgebra::base::DMatrix::repeat(adaptaters.len() + 2, adaptaters.len() + 2, 0);
     let mut seen = HashMap::new();
@@ -48,7 +56,10 @@ fn chain_count(adaptaters: &[u64]) -> u64 {
         }
     }
 
-    let device_jolt = *adaptaters.last().unwrap() + 3;
+    let device_jolt = adaptaters
+        .last()
+        .ok_or_else(|| color_eyre::eyre::eyre!("There are no adapters"))?
+        + 3;
     seen.insert(device_jolt, adaptaters.len() + 1);
     for j in 1..=3 {
         if let Some(&idx) = seen.get(&(device_jolt - j)) {
@@ -64,7 +75,7 @@ fn chain_count(adaptaters: &[u64]) -> u64 {
         adj *= &matrix;
     }
 
-    count
+    Ok(count)
 }
 
 #[cfg(test)]
@@ -77,28 +88,29 @@ mod test {
     fn day10_simple_p1() {
         let mut input = SIMPLE.to_owned();
         input.sort();
-        assert_eq!(differences(&input), (7, 5))
+        assert_eq!(differences(&input).unwrap(), (7, 5))
     }
 
     #[test]
     fn day10_simple_p2() {
         let mut input = SIMPLE.to_owned();
         input.sort();
-        assert_eq!(chain_count(&input), 8)
+        assert_eq!(chain_count(&input).unwrap(), 8)
     }
 }
 
-pub fn part_2(input: Input) -> color_eyre::Result<String> {
-    input.sort();
-    let result = chain_count(input);
-    Ok(format!("Number of arangements: {}", result))
+pub fn part_2(input: Input) -> color_eyre::Result<Answer> {
+    let result = chain_count(input)?;
+    Ok(Answer::new(result).with_description(format!("Number of arangements: {}", result)))
 }
 
 pub fn parsing(context: &mut DayContext) -> color_eyre::Result<Vec<u64>> {
-    context.parse_lines(|s| Ok(s.parse()?))
+    let mut adapters = context.parse_lines(|s| Ok(s.parse()?))?;
+    adapters.sort_unstable();
+    Ok(adapters)
 }
 
 pub fn execute(context: &mut DayContext) -> color_eyre::Result<()> {
-    let mut input = parsing(context)?;
-    context.execute(input.as_mut(), part_1, part_2)
+    let input = parsing(context)?;
+    context.execute(input.as_slice(), part_1, part_2)
 }
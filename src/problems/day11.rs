@@ -1,17 +1,17 @@
-use std::cell::Cell;
-
-use crate::DayContext;
+use crate::{grid::DIRECTIONS_8, Answer, DayContext, Grid};
 
 type Input = AeroportGame;
 
-pub fn part_1(mut seats: Input) -> color_eyre::Result<String> {
+pub fn part_1(mut seats: Input) -> color_eyre::Result<Answer> {
     while seats.step() {}
-    Ok(format!("Free seats at the end: {}", seats.count_full()))
+    let full = seats.count_full();
+    Ok(Answer::new(full).with_description(format!("Free seats at the end: {}", full)))
 }
 
-pub fn part_2(mut seats: Input) -> color_eyre::Result<String> {
+pub fn part_2(mut seats: Input) -> color_eyre::Result<Answer> {
     while seats.step_visible() {}
-    Ok(format!("Free seats at the end: {}", seats.count_full()))
+    let full = seats.count_full();
+    Ok(Answer::new(full).with_description(format!("Free seats at the end: {}", full)))
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -21,48 +21,48 @@ enum State {
     Full,
 }
 
+impl std::fmt::Display for State {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let seat = match self {
+            State::Floor => ".",
+            State::Empty => "L",
+            State::Full => "#",
+        };
+        write!(f, "{}", seat)
+    }
+}
+
+#[derive(Clone)]
 pub struct AeroportGame {
-    grid: Vec<Vec<Cell<State>>>,
+    grid: Grid<State>,
 }
 
 impl std::fmt::Debug for AeroportGame {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for line in &self.grid {
-            for seat in line {
-                let seat = match seat.get() {
-                    State::Floor => ".",
-                    State::Empty => "L",
-                    State::Full => "#",
-                };
-                write!(f, "{}", seat)?;
-            }
-            write!(f, "\n")?;
-        }
-
-        Ok(())
+        write!(f, "{}", self.grid)
     }
 }
 
 impl AeroportGame {
-    pub fn step(&mut self) -> bool {
-        let new_grid = self.grid.clone();
+    /// Applies the rules to every seat at once, returns whether a seat changed
+    fn step_with<F: Fn(&Self, usize, usize) -> usize>(
+        &mut self,
+        occupied: F,
+        tolerance: usize,
+    ) -> bool {
+        let mut new_grid = self.grid.clone();
         let mut changed = false;
 
-        for (i, line) in new_grid.iter().enumerate() {
-            for (j, seat) in line.iter().enumerate() {
-                let new_state = match seat.get() {
-                    State::Floor => State::Floor,
-                    State::Empty if self.neighbours(i, j) == 0 => {
-                        changed = true;
-                        State::Full
-                    }
-                    State::Full if self.neighbours(i, j) >= 4 => {
-                        changed = true;
-                        State::Empty
-                    }
-                    s => s,
-                };
-                seat.set(new_state);
+        for ((x, y), seat) in self.grid.iter() {
+            let new_state = match seat {
+                State::Floor => State::Floor,
+                State::Empty if occupied(self, x, y) == 0 => State::Full,
+                State::Full if occupied(self, x, y) >= tolerance => State::Empty,
+                &s => s,
+            };
+            if new_state != *seat {
+                changed = true;
+                new_grid[(x, y)] = new_state;
             }
         }
 
@@ -70,149 +70,86 @@ impl AeroportGame {
         changed
     }
 
-    pub fn step_visible(&mut self) -> bool {
-        let new_grid = self.grid.clone();
-        let mut changed = false;
-
-        for (i, line) in new_grid.iter().enumerate() {
-            for (j, seat) in line.iter().enumerate() {
-                let new_state = match seat.get() {
-                    State::Floor => State::Floor,
-                    State::Empty if self.visible(i, j) == 0 => {
-                        changed = true;
-                        State::Full
-                    }
-                    State::Full if self.visible(i, j) >= 5 => {
-                        changed = true;
-                        State::Empty
-                    }
-                    s => s,
-                };
-                seat.set(new_state);
-            }
-        }
+    pub fn step(&mut self) -> bool {
+        self.step_with(Self::neighbours, 4)
+    }
 
-        self.grid = new_grid;
-        changed
+    pub fn step_visible(&mut self) -> bool {
+        self.step_with(Self::visible, 5)
     }
 
     fn count_full(&self) -> usize {
         self.grid
             .iter()
-            .map(|l| l.iter().filter(|s| s.get() == State::Full).count())
-            .sum()
+            .filter(|(_, &seat)| seat == State::Full)
+            .count()
     }
 
-    fn neighbours(&self, i: usize, j: usize) -> usize {
-        let mut count = 0;
-
-        let istart = if i == 0 { 1 } else { 0 };
-        let iend = if i == self.grid.len() - 1 { 2 } else { 3 };
-
-        let jstart = if j == 0 { 1 } else { 0 };
-        let jend = if j == self.grid[0].len() - 1 { 2 } else { 3 };
-
-        for di in istart..iend {
-            for dj in jstart..jend {
-                // Same cell
-                if di == 1 && dj == 1 {
-                    continue;
-                }
-
-                match self.grid[i + di - 1][j + dj - 1].get() {
-                    State::Full => count += 1,
-                    _ => (),
-                }
-            }
-        }
-
-        count
+    fn neighbours(&self, x: usize, y: usize) -> usize {
+        self.grid
+            .neighbours_8(x, y)
+            .filter(|&position| self.grid[position] == State::Full)
+            .count()
     }
 
-    fn visible(&self, i: usize, j: usize) -> usize {
-        let mut count = 0;
-
-        for di in -1i64..=1 {
-            for dj in -1..=1 {
-                if di == 0 && dj == 0 {
-                    continue;
-                }
-                for k in 1i64.. {
-                    let vi = i as i64 + (di * k);
-                    if vi < 0 || vi as usize >= self.grid.len() {
-                        break;
-                    }
-                    let vi = vi as usize;
-
-                    let vj = j as i64 + (dj * k);
-                    if vj < 0 || vj as usize >= self.grid[0].len() {
-                        break;
-                    }
-                    let vj = vj as usize;
-
-                    match self.grid[vi][vj].get() {
-                        State::Full => {
-                            count += 1;
-                            break;
-                        }
-                        State::Empty => break,
-                        State::Floor => continue,
-                    }
-                }
-            }
-        }
-
-        count
+    fn visible(&self, x: usize, y: usize) -> usize {
+        DIRECTIONS_8
+            .iter()
+            .filter(|&&direction| {
+                self.grid
+                    .ray(x, y, direction)
+                    .map(|position| self.grid[position])
+                    .find(|&seat| seat != State::Floor)
+                    == Some(State::Full)
+            })
+            .count()
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::{AeroportGame, State};
-    use std::cell::Cell;
+    use crate::Grid;
 
     fn simple() -> AeroportGame {
-        let mut grid = Vec::new();
-        for _ in 0..10 {
-            grid.push(vec![Cell::new(State::Empty); 10]);
-        }
-        grid[0][1].set(State::Floor);
-        grid[0][4].set(State::Floor);
-        grid[0][7].set(State::Floor);
+        let mut grid = Grid::filled(10, 10, State::Empty);
+        grid[(1, 0)] = State::Floor;
+        grid[(4, 0)] = State::Floor;
+        grid[(7, 0)] = State::Floor;
 
-        grid[1][7].set(State::Floor);
+        grid[(7, 1)] = State::Floor;
 
-        grid[2][1].set(State::Floor);
-        grid[2][3].set(State::Floor);
-        grid[2][5].set(State::Floor);
-        grid[2][6].set(State::Floor);
-        grid[2][8].set(State::Floor);
-        grid[2][9].set(State::Floor);
+        grid[(1, 2)] = State::Floor;
+        grid[(3, 2)] = State::Floor;
+        grid[(5, 2)] = State::Floor;
+        grid[(6, 2)] = State::Floor;
+        grid[(8, 2)] = State::Floor;
+        grid[(9, 2)] = State::Floor;
 
-        grid[3][4].set(State::Floor);
-        grid[3][7].set(State::Floor);
+        grid[(4, 3)] = State::Floor;
+        grid[(7, 3)] = State::Floor;
 
-        grid[4][1].set(State::Floor);
-        grid[4][4].set(State::Floor);
-        grid[4][7].set(State::Floor);
+        grid[(1, 4)] = State::Floor;
+        grid[(4, 4)] = State::Floor;
+        grid[(7, 4)] = State::Floor;
 
-        grid[5][1].set(State::Floor);
-        grid[5][7].set(State::Floor);
+        grid[(1, 5)] = State::Floor;
+        grid[(7, 5)] = State::Floor;
 
-        grid[6][0].set(State::Floor);
-        grid[6][1].set(State::Floor);
-        grid[6][3].set(State::Floor);
-        grid[6][5].set(State::Floor);
-        grid[6][6].set(State::Floor);
-        grid[6][7].set(State::Floor);
-        grid[6][8].set(State::Floor);
-        grid[6][9].set(State::Floor);
+        grid[(0, 6)] = State::Floor;
+        grid[(1, 6)] = State::Floor;
+        grid[(3, 6)] = State::Floor;
+        grid[(5, 6)] = State::Floor;
+        grid[(6, 6)] = State::Floor;
+        grid[(7, 6)] = State::Floor;
+        grid[(8, 6)] = State::Floor;
+        grid[(9, 6)] = State::Floor;
 
-        grid[8][1].set(State::Floor);
-        grid[8][8].set(State::Floor);
+        grid[(1, 8)] = State::Floor;
+        grid[(8, 8)] = State::Floor;
 
-        grid[9][1].set(State::Floor);
-        grid[9][7].set(State::Floor);
+        grid[(1, 9)] = State::Floor;
+        grid[(7, 9)] = State::Floor;
 
         AeroportGame { grid }
     }
@@ -248,14 +185,10 @@ mod test {
 }
 
 pub fn parsing(ctx: &mut DayContext) -> color_eyre::Result<Input> {
-    let grid = ctx.parse_byte_lines(|line| {
-        line.iter()
-            .map(|i| match i {
-                b'.' => Ok(Cell::new(State::Floor)),
-                b'L' => Ok(Cell::new(State::Empty)),
-                _ => color_eyre::eyre::bail!("No such seat kind"),
-            })
-            .collect()
+    let grid = ctx.parse_grid(|seat| match seat {
+        b'.' => Ok(State::Floor),
+        b'L' => Ok(State::Empty),
+        _ => color_eyre::eyre::bail!("No such seat kind"),
     })?;
 
     Ok(AeroportGame { grid })
@@ -1,15 +1,20 @@
-use crate::DayContext;
+use crate::{Answer, DayContext};
 
 type Input = Ship;
 
-pub fn part_1(mut ship: Input) -> color_eyre::Result<String> {
+pub fn part_1(mut ship: Input) -> color_eyre::Result<Answer> {
     ship.read_controls();
-    Ok(format!("Distance travelled: {}", ship.distance_traveled()))
+    let distance = ship.distance_traveled();
+    Ok(Answer::new(distance).with_description(format!("Distance travelled: {}", distance)))
 }
 
-pub fn part_2(mut ship: Input) -> color_eyre::Result<String> {
+pub fn part_2(mut ship: Input) -> color_eyre::Result<Answer> {
     ship.read_waypoint_controls();
-    Ok(format!("Distance travelled following waypoint: {}", ship.distance_traveled()))
+    let distance = ship.distance_traveled();
+    Ok(Answer::new(distance).with_description(format!(
+        "Distance travelled following waypoint: {}",
+        distance
+    )))
 }
 
 #[derive(Clone, Copy)]
@@ -51,6 +56,7 @@ impl Direction {
     }
 }
 
+#[derive(Clone)]
 pub struct Ship {
     controls: Vec<Control>,
     facing: Direction,
@@ -1,4 +1,4 @@
-use crate::DayContext;
+use crate::{Answer, DayContext};
 
 type Input = Timetable;
 
@@ -6,7 +6,7 @@ type Input = Timetable;
 // N = P + B
 // W = N - X = P + B - X = X- (X % B) + B - X = B - (X % B)
 
-pub fn part_1(tt: Input) -> color_eyre::Result<String> {
+pub fn part_1(tt: Input) -> color_eyre::Result<Answer> {
     let (bus, wait) = tt
         .cycles
         .iter()
@@ -21,7 +21,8 @@ pub fn part_1(tt: Input) -> color_eyre::Result<String> {
         .min_by_key(|&(_, tm)| tm)
         .ok_or_else(|| color_eyre::eyre::eyre!("No element in the cycle"))?;
 
-    Ok(format!("ID*wait time: {}", bus * wait))
+    let result = bus * wait;
+    Ok(Answer::new(result).with_description(format!("ID*wait time: {}", result)))
 }
 
 fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
@@ -33,25 +34,28 @@ fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
     }
 }
 
-fn mod_inv(x: i64, m: i64) -> i64 {
+fn mod_inv(x: i64, m: i64) -> Option<i64> {
     let (g, x, _) = egcd(x, m);
     if g == 1 {
-        (x % m + m) % m
+        Some((x % m + m) % m)
     } else {
-        panic!("Input are coprime")
+        None
     }
 }
 
-fn chinese_remainder(residues: &[i64], moduli: &[i64]) -> i64 {
+fn chinese_remainder(residues: &[i64], moduli: &[i64]) -> color_eyre::Result<i64> {
     let n: i64 = moduli.iter().product();
 
     let mut sum = 0;
     for (&mod_i, &b_i) in residues.iter().zip(moduli) {
         let p = n / b_i;
-        sum += mod_i * mod_inv(p, b_i) * p;
+        let inv = mod_inv(p, b_i).ok_or_else(|| {
+            color_eyre::eyre::eyre!("Bus {} is not coprime with the other buses", b_i)
+        })?;
+        sum += mod_i * inv * p;
     }
 
-    sum % n
+    Ok(sum % n)
 }
 
 // find t such that
@@ -61,7 +65,7 @@ fn chinese_remainder(residues: &[i64], moduli: &[i64]) -> i64 {
 // t = b_i - i (mod b_i)
 // t = mod_i (mod b_i)
 
-fn sequential_leaving(cycles: &[Bus]) -> i64 {
+fn sequential_leaving(cycles: &[Bus]) -> color_eyre::Result<i64> {
     let (moduli, residue): (Vec<_>, Vec<_>) = cycles
         .iter()
         .enumerate()
@@ -81,9 +85,9 @@ fn sequential_leaving(cycles: &[Bus]) -> i64 {
     chinese_remainder(&residue, &moduli)
 }
 
-pub fn part_2(tt: Input) -> color_eyre::Result<String> {
-    let solution = sequential_leaving(&tt.cycles);
-    Ok(format!("Such timestamp is: {}", solution))
+pub fn part_2(tt: Input) -> color_eyre::Result<Answer> {
+    let solution = sequential_leaving(&tt.cycles)?;
+    Ok(Answer::new(solution).with_description(format!("Such timestamp is: {}", solution)))
 }
 
 #[cfg(test)]
@@ -99,11 +103,11 @@ mod test {
             Bus::Present(19),
         ];
 
-        assert_eq!(sequential_leaving(&cycles), 3417);
+        assert_eq!(sequential_leaving(&cycles).unwrap(), 3417);
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Timetable {
     current_time: u64,
     cycles: Vec<Bus>,
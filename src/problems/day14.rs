@@ -1,21 +1,23 @@
 use std::{collections::HashMap, str::FromStr};
 
-use crate::DayContext;
+use crate::{Answer, DayContext};
 
 type Input = Vec<Instr>;
 
-pub fn part_1(input: Input) -> color_eyre::Result<String> {
+pub fn part_1(input: Input) -> color_eyre::Result<Answer> {
     let mut computer = Computer::new();
     computer.execute(&input);
 
-    Ok(format!("The sum of the memory is: {}", computer.sum()))
+    let sum = computer.sum();
+    Ok(Answer::new(sum).with_description(format!("The sum of the memory is: {}", sum)))
 }
 
-pub fn part_2(input: Input) -> color_eyre::Result<String> {
+pub fn part_2(input: Input) -> color_eyre::Result<Answer> {
     let mut computer = Computer::new();
     computer.execute_v2(&input);
 
-    Ok(format!("The sum of the memory is: {}", computer.sum()))
+    let sum = computer.sum();
+    Ok(Answer::new(sum).with_description(format!("The sum of the memory is: {}", sum)))
 }
 
 struct Computer {
@@ -151,6 +153,7 @@ impl FromStr for Mask {
     }
 }
 
+#[derive(Clone)]
 pub enum Instr {
     Mask(Mask),
     Set { address: u64, value: u64 },
@@ -1,11 +1,12 @@
-use crate::DayContext;
+use crate::{Answer, DayContext};
 use std::collections::HashMap;
 
 type Input = Vec<u64>;
 
-pub fn part_1(start: Input) -> color_eyre::Result<String> {
+pub fn part_1(start: Input) -> color_eyre::Result<Answer> {
     let mut seq = Sequence::new(&start);
-    Ok(format!("The 2020 term is: {}", seq.step_until(2020)))
+    let term = seq.step_until(2020);
+    Ok(Answer::new(term).with_description(format!("The 2020 term is: {}", term)))
 }
 
 struct Sequence {
@@ -85,9 +86,10 @@ mod test {
     }
 }
 
-pub fn part_2(start: Input) -> color_eyre::Result<String> {
+pub fn part_2(start: Input) -> color_eyre::Result<Answer> {
     let mut seq = Sequence::new(&start);
-    Ok(format!("The 2020 term is: {}", seq.step_until(30000000)))
+    let term = seq.step_until(30000000);
+    Ok(Answer::new(term).with_description(format!("The 2020 term is: {}", term)))
 }
 
 pub fn parsing(context: &mut DayContext) -> color_eyre::Result<Input> {
@@ -1,4 +1,4 @@
-use crate::DayContext;
+use crate::{Answer, DayContext};
 use std::{
     collections::{HashMap, HashSet},
     ops::Range,
@@ -6,7 +6,7 @@ use std::{
 
 type Input = Scan;
 
-pub fn part_1(scan: Input) -> color_eyre::Result<String> {
+pub fn part_1(scan: Input) -> color_eyre::Result<Answer> {
     let error_rate: u64 = scan
         .scanned
         .iter()
@@ -24,7 +24,7 @@ pub fn part_1(scan: Input) -> color_eyre::Result<String> {
         .map(|invalid| invalid.into_iter().sum::<u64>())
         .sum();
 
-    Ok(format!("Error rate is: {}", error_rate))
+    Ok(Answer::new(error_rate).with_description(format!("Error rate is: {}", error_rate)))
 }
 
 fn associate_field<'f, 't>(
@@ -51,7 +51,7 @@ fn associate_field<'f, 't>(
     remaining_fields.into_iter().nth(0).copied()
 }
 
-pub fn part_2(scan: Input) -> color_eyre::Result<String> {
+pub fn part_2(scan: Input) -> color_eyre::Result<Answer> {
     let valid_tickets: Vec<_> = scan
         .scanned
         .iter()
@@ -66,6 +66,7 @@ pub fn part_2(scan: Input) -> color_eyre::Result<String> {
     let mut fields = HashMap::new();
 
     while !remaining_fields.is_empty() {
+        let assigned = fields.len();
         for field_number in 0..scan.own_ticket.len() {
             let field = associate_field(&remaining_fields, &valid_tickets, field_number);
             if let Some(field) = field {
@@ -73,6 +74,15 @@ pub fn part_2(scan: Input) -> color_eyre::Result<String> {
                 fields.insert(field, field_number);
             }
         }
+
+        if fields.len() == assigned {
+            let mut remaining: Vec<_> = remaining_fields.iter().map(|f| f.name.as_str()).collect();
+            remaining.sort_unstable();
+            color_eyre::eyre::bail!(
+                "Could not reduce the columns of the fields {}",
+                remaining.join(", ")
+            );
+        }
     }
 
     let field_product: u64 = fields
@@ -81,10 +91,11 @@ pub fn part_2(scan: Input) -> color_eyre::Result<String> {
         .map(|(_, &number)| scan.own_ticket[number])
         .product();
 
-    Ok(format!("The product of the fields is: {}", field_product))
+    Ok(Answer::new(field_product)
+        .with_description(format!("The product of the fields is: {}", field_product)))
 }
 
-#[derive(Debug, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
 struct Field {
     name: String,
     range_a: Range<u64>,
@@ -97,6 +108,7 @@ impl Field {
     }
 }
 
+#[derive(Clone)]
 pub struct Scan {
     fields: Vec<Field>,
     own_ticket: Vec<u64>,
@@ -109,49 +121,65 @@ fn parse_range(range: &str) -> color_eyre::Result<Range<u64>> {
     Ok((start.parse()?)..(end.parse::<u64>()? + 1))
 }
 
+fn parse_field(line: &str) -> color_eyre::Result<Field> {
+    let (name, ranges) = crate::split_string_separator(line, ':')
+        .ok_or_else(|| color_eyre::eyre::eyre!("Malformed field: {}", line))?;
+    let (range_a, range_b) = crate::large_split_str_sep(ranges, " or ")
+        .ok_or_else(|| color_eyre::eyre::eyre!("Malformed ranges: {}", ranges))?;
+    Ok(Field {
+        name: name.to_owned(),
+        range_a: parse_range(range_a.trim_start())?,
+        range_b: parse_range(range_b)?,
+    })
+}
+
+/// Parses the tickets of a group, after its header
+fn parse_tickets(group: &[String], header: &str) -> color_eyre::Result<Vec<Vec<u64>>> {
+    match group.split_first() {
+        Some((first, tickets)) if first == header => tickets
+            .iter()
+            .map(|line| {
+                line.split(',')
+                    .map(|x| Ok(x.parse()?))
+                    .collect::<color_eyre::Result<_>>()
+            })
+            .collect(),
+        _ => color_eyre::eyre::bail!("Expected the tickets to start with {:?}", header),
+    }
+}
+
 pub fn parsing(context: &mut DayContext) -> color_eyre::Result<Input> {
     let mut fields = Vec::new();
-    let mut done_fields = false;
     let mut own_ticket = None;
     let mut scanned = Vec::new();
 
-    context.accumulate_str_lines(|_, line| {
-        if !done_fields {
-            if line == "" {
-                done_fields = true;
-                return Ok(());
+    context.accumulate_groups(|index, group| {
+        match index {
+            0 => {
+                fields = group
+                    .iter()
+                    .map(|line| parse_field(line))
+                    .collect::<Result<_, _>>()?
             }
-            let (name, ranges) = crate::split_string_separator(line, ':')
-                .ok_or_else(|| color_eyre::eyre::eyre!("Malformed field: {}", line))?;
-            let (range_a, range_b) = crate::large_split_str_sep(ranges, " or ")
-                .ok_or_else(|| color_eyre::eyre::eyre!("Malformed ranges: {}", ranges))?;
-            let field = Field {
-                name: name.to_owned(),
-                range_a: parse_range(range_a.trim_start())?,
-                range_b: parse_range(range_b)?,
-            };
-            fields.push(field);
-        } else {
-            if own_ticket.is_none() {
-                if line == "your ticket:" {
-                    return Ok(());
-                } else {
-                    own_ticket = Some(
-                        line.split(',')
-                            .map(|x| x.parse())
-                            .collect::<Result<_, _>>()?,
+            1 => own_ticket = parse_tickets(group, "your ticket:")?.pop(),
+            2 => {
+                scanned = parse_tickets(group, "nearby tickets:")?;
+                let expected = own_ticket.as_ref().map(Vec::len);
+                if let Some((i, ticket)) = scanned
+                    .iter()
+                    .enumerate()
+                    .find(|(_, ticket)| expected.is_some_and(|n| ticket.len() != n))
+                {
+                    color_eyre::eyre::bail!(
+                        "Nearby ticket {} has {} values, expected {} like your ticket",
+                        i + 1,
+                        ticket.len(),
+                        expected.unwrap_or_default()
                     );
                 }
-            } else {
-                if !(line == "nearby tickets:" || line == "") {
-                    scanned.push(
-                        line.split(',')
-                            .map(|x| x.parse())
-                            .collect::<Result<_, _>>()?,
-                    )
-                }
             }
-        };
+            _ => color_eyre::eyre::bail!("Unexpected group of lines after the nearby tickets"),
+        }
         Ok(())
     })?;
 
@@ -167,3 +195,42 @@ pub fn execute(context: &mut DayContext) -> color_eyre::Result<()> {
     let input = parsing(context)?;
     context.execute(input, part_1, part_2)
 }
+
+#[cfg(test)]
+mod test {
+    use super::{parsing, part_2};
+    use crate::{DayContext, Parts};
+
+    #[test]
+    fn ambiguous_fields() {
+        let input = "departure a: 0-1 or 4-19\n\
+                     departure b: 0-1 or 4-19\n\
+                     \n\
+                     your ticket:\n\
+                     11,12\n\
+                     \n\
+                     nearby tickets:\n\
+                     3,9\n\
+                     15,1\n";
+        let scan = parsing(&mut DayContext::from_bytes(input, Parts::Both)).unwrap();
+        let error = part_2(scan).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Could not reduce the columns of the fields departure a, departure b"
+        );
+    }
+
+    #[test]
+    fn short_nearby_ticket() {
+        let input = "class: 0-1 or 4-19\n\
+                     row: 0-5 or 8-19\n\
+                     \n\
+                     your ticket:\n\
+                     11,12\n\
+                     \n\
+                     nearby tickets:\n\
+                     3,9\n\
+                     15\n";
+        assert!(parsing(&mut DayContext::from_bytes(input, Parts::Both)).is_err());
+    }
+}
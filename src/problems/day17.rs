@@ -1,30 +1,33 @@
-use crate::DayContext;
+use crate::{Answer, DayContext};
 use itertools::{izip, Itertools};
 use std::{collections::HashSet, ops::Range};
 
 type Input = ConwayCube;
 
-pub fn part_1(mut cube: Input) -> color_eyre::Result<String> {
+pub fn part_1(mut cube: Input) -> color_eyre::Result<Answer> {
     cube.inc_dim();
 
     for _ in 0..6 {
         cube.step()
     }
-    Ok(format!("Active cubes after boot: {}", cube.active()))
+    let active = cube.active();
+    Ok(Answer::new(active).with_description(format!("Active cubes after boot: {}", active)))
 }
 
-pub fn part_2(mut cube: Input) -> color_eyre::Result<String> {
+pub fn part_2(mut cube: Input) -> color_eyre::Result<Answer> {
     cube.inc_dim();
     cube.inc_dim();
 
     for _ in 0..6 {
         cube.step()
     }
-    Ok(format!("Active hyper cubes after boot: {}", cube.active()))
+    let active = cube.active();
+    Ok(Answer::new(active).with_description(format!("Active hyper cubes after boot: {}", active)))
 }
 
 // The spread is one more than the largest
 // It would be nicer with const generics
+#[derive(Clone)]
 pub struct ConwayCube {
     dim: usize,
     active: HashSet<Vec<i64>>,
@@ -100,26 +103,22 @@ impl ConwayCube {
 }
 
 pub fn parsing(context: &mut DayContext) -> color_eyre::Result<Input> {
-    let mut active = HashSet::new();
-    let mut x_spread = -1..0;
-    let mut y_spread = -1..1;
-
-    context.accumulate_byte_lines(|y, line| {
-        line.iter()
-            .enumerate()
-            .filter_map(|(x, &c)| if c == b'#' { Some(x) } else { None })
-            .for_each(|x| {
-                active.insert(vec![x as i64, y as i64]);
-            });
-        x_spread.end = line.len() as i64 + 1;
-        y_spread.end += 1;
-        Ok(())
+    let grid = context.parse_grid(|cell| match cell {
+        b'#' => Ok(true),
+        b'.' => Ok(false),
+        _ => color_eyre::eyre::bail!("Unknown cube state"),
     })?;
 
+    let active = grid
+        .iter()
+        .filter(|(_, &active)| active)
+        .map(|((x, y), _)| vec![x as i64, y as i64])
+        .collect();
+
     Ok(ConwayCube {
         active,
         dim: 2,
-        spreads: vec![x_spread, y_spread],
+        spreads: vec![-1..grid.width() as i64 + 1, -1..grid.height() as i64 + 1],
     })
 }
 
@@ -1,24 +1,37 @@
-use crate::DayContext;
+use crate::{Answer, DayContext};
+use bstr::ByteSlice;
 use lalrpop_util::lalrpop_mod;
 
 lalrpop_mod!(expr_parser, "/problems/day18.rs");
 
-type Input = Vec<String>;
+type Input<'i> = Vec<&'i str>;
 
-pub fn part_1(exprs: Input) -> color_eyre::Result<String> {
-    let sum: u64 = exprs
+pub fn part_1(exprs: Input) -> color_eyre::Result<Answer> {
+    let parser = expr_parser::NoPrecExprParser::new();
+    let sum = exprs
         .iter()
-        .map(|s| expr_parser::NoPrecExprParser::new().parse(s).unwrap())
-        .sum();
-    Ok(format!("Sum of all exprs is: {}", sum))
+        .enumerate()
+        .map(|(i, s)| {
+            parser
+                .parse(s)
+                .map_err(|e| color_eyre::eyre::eyre!("Invalid expression on line {}: {}", i + 1, e))
+        })
+        .sum::<color_eyre::Result<u64>>()?;
+    Ok(Answer::new(sum).with_description(format!("Sum of all exprs is: {}", sum)))
 }
 
-pub fn part_2(exprs: Input) -> color_eyre::Result<String> {
-    let sum: u64 = exprs
+pub fn part_2(exprs: Input) -> color_eyre::Result<Answer> {
+    let parser = expr_parser::InvPrecExprParser::new();
+    let sum = exprs
         .iter()
-        .map(|s| expr_parser::InvPrecExprParser::new().parse(s).unwrap())
-        .sum();
-    Ok(format!("Sum of all exprs is: {}", sum))
+        .enumerate()
+        .map(|(i, s)| {
+            parser
+                .parse(s)
+                .map_err(|e| color_eyre::eyre::eyre!("Invalid expression on line {}: {}", i + 1, e))
+        })
+        .sum::<color_eyre::Result<u64>>()?;
+    Ok(Answer::new(sum).with_description(format!("Sum of all exprs is: {}", sum)))
 }
 
 #[cfg(test)]
@@ -56,11 +69,12 @@ mod test {
     }
 }
 
-pub fn parsing(context: &mut DayContext) -> color_eyre::Result<Input> {
-    context.parse_lines(|s| Ok(s.to_string()))
+pub fn parsing<'i>(context: &mut DayContext, input: &'i [u8]) -> color_eyre::Result<Input<'i>> {
+    context.parse_borrowed_lines(input, |line| Ok(line.to_str()?))
 }
 
 pub fn execute(context: &mut DayContext) -> color_eyre::Result<()> {
-    let input = parsing(context)?;
+    let input = context.take_input_bytes()?;
+    let input = parsing(context, &input)?;
     context.execute(input, part_1, part_2)
 }
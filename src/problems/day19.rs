@@ -1,54 +1,54 @@
-use crate::DayContext;
+use crate::{Answer, DayContext};
+use color_eyre::eyre::Context;
 use std::collections::{HashMap, HashSet};
 
 type Input = (Rules, Vec<String>);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Rule {
     Letter(char),
     Sequence(Vec<u64>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Rules {
     rules: HashMap<u64, Vec<Rule>>,
 }
 
 impl Rules {
-    fn matches(&self, input: &str) -> bool {
-        match self.munch(input, 0) {
-            None => false,
-            Some(s) => s.is_empty(),
-        }
+    fn rule(&self, rule: u64) -> color_eyre::Result<&[Rule]> {
+        self.rules
+            .get(&rule)
+            .map(Vec::as_slice)
+            .ok_or_else(|| color_eyre::eyre::eyre!("Unknown rule {}", rule))
     }
 
-    fn munch<'i>(&self, input: &'i str, rule: u64) -> Option<&'i str> {
-        match self.rules.get(&rule) {
-            None => panic!("Unknown rule reached"),
-            Some(rules) => {
-                'outer: for rule in rules {
-                    match rule {
-                        Rule::Letter(l) => {
-                            let striped = input.strip_prefix(|c| c == *l);
-                            if striped.is_some() {
-                                return striped;
-                            }
-                        }
-                        Rule::Sequence(s) => {
-                            let mut inp = input;
-                            for r in s {
-                                match self.munch(inp, *r) {
-                                    None => continue 'outer,
-                                    Some(out) => inp = out,
-                                }
-                            }
-                            return Some(inp);
+    fn matches(&self, input: &str) -> color_eyre::Result<bool> {
+        Ok(self.munch(input, 0)? == Some(""))
+    }
+
+    fn munch<'i>(&self, input: &'i str, rule: u64) -> color_eyre::Result<Option<&'i str>> {
+        'outer: for rule in self.rule(rule)? {
+            match rule {
+                Rule::Letter(l) => {
+                    let striped = input.strip_prefix(|c| c == *l);
+                    if striped.is_some() {
+                        return Ok(striped);
+                    }
+                }
+                Rule::Sequence(s) => {
+                    let mut inp = input;
+                    for r in s {
+                        match self.munch(inp, *r)? {
+                            None => continue 'outer,
+                            Some(out) => inp = out,
                         }
                     }
+                    return Ok(Some(inp));
                 }
-                None
             }
         }
+        Ok(None)
     }
 
     fn looping_matches(
@@ -56,9 +56,9 @@ impl Rules {
         input: &str,
         rule_42: &HashSet<String>,
         rule_31: &HashSet<String>,
-    ) -> bool {
-        let ret = self.looping_munch(&vec![input], 0, rule_42, rule_31);
-        !ret.is_empty() && ret.iter().any(|s| s.len() == 0)
+    ) -> color_eyre::Result<bool> {
+        let ret = self.looping_munch(&vec![input], 0, rule_42, rule_31)?;
+        Ok(!ret.is_empty() && ret.iter().any(|s| s.len() == 0))
     }
 
     fn looping_munch<'i>(
@@ -67,10 +67,10 @@ impl Rules {
         rule: u64,
         rule_42: &HashSet<String>,
         rule_31: &HashSet<String>,
-    ) -> Vec<&'i str> {
+    ) -> color_eyre::Result<Vec<&'i str>> {
         let mut remains = Vec::new();
         if inputs.is_empty() {
-            return remains;
+            return Ok(remains);
         }
 
         if rule == 8 {
@@ -89,7 +89,14 @@ impl Rules {
                 rem = new_rem;
             }
         } else if rule == 11 {
-            let len = rule_31.iter().nth(0).unwrap().len() + rule_42.iter().nth(0).unwrap().len();
+            let production_len = |productions: &HashSet<String>| {
+                productions
+                    .iter()
+                    .next()
+                    .map(String::len)
+                    .ok_or_else(|| color_eyre::eyre::eyre!("Rules 42 and 31 can't be empty"))
+            };
+            let len = production_len(rule_31)? + production_len(rule_42)?;
             let max_input_len = inputs.iter().map(|s| s.len()).max().unwrap();
             let mut k = 1;
             while k * len <= max_input_len {
@@ -105,8 +112,7 @@ impl Rules {
             }
         } else {
             for input in inputs {
-                let rules = self.rules.get(&rule).unwrap();
-                for rule in rules {
+                for rule in self.rule(rule)? {
                     match rule {
                         Rule::Letter(l) => {
                             let striped = input.strip_prefix(|c| c == *l);
@@ -117,7 +123,7 @@ impl Rules {
                         Rule::Sequence(s) => {
                             let mut rem = vec![*input];
                             for r in s {
-                                rem = self.looping_munch(&rem, *r, rule_42, rule_31);
+                                rem = self.looping_munch(&rem, *r, rule_42, rule_31)?;
                             }
                             remains = rem;
                         }
@@ -126,38 +132,43 @@ impl Rules {
             }
         }
 
-        remains
+        Ok(remains)
     }
 
-    fn productions(&self, rule: u64) -> HashSet<String> {
-        let parts = self.rules.get(&rule).unwrap();
+    fn productions(&self, rule: u64) -> color_eyre::Result<HashSet<String>> {
+        let parts = self.rule(rule)?;
 
         parts
             .iter()
-            .map(|part| match part {
-                Rule::Letter(s) => {
-                    let mut set = HashSet::new();
-                    set.insert(format!("{}", s));
-                    set
-                }
-                Rule::Sequence(s) => s.iter().copied().map(|r| self.productions(r)).fold(
-                    {
+            .map(|part| -> color_eyre::Result<HashSet<String>> {
+                match part {
+                    Rule::Letter(s) => {
                         let mut set = HashSet::new();
-                        set.insert(String::new());
-                        set
-                    },
-                    |current, seq| {
-                        current
-                            .into_iter()
-                            .map(|prefix| seq.iter().map(move |suffix| prefix.to_owned() + suffix))
-                            .flatten()
-                            .collect()
-                    },
-                ),
+                        set.insert(format!("{}", s));
+                        Ok(set)
+                    }
+                    Rule::Sequence(s) => s.iter().copied().map(|r| self.productions(r)).try_fold(
+                        {
+                            let mut set = HashSet::new();
+                            set.insert(String::new());
+                            set
+                        },
+                        |current, seq| {
+                            let seq = seq?;
+                            Ok(current
+                                .into_iter()
+                                .map(|prefix| {
+                                    seq.iter().map(move |suffix| prefix.to_owned() + suffix)
+                                })
+                                .flatten()
+                                .collect())
+                        },
+                    ),
+                }
             })
-            .fold(HashSet::new(), |mut current, part| {
-                current.extend(part);
-                current
+            .try_fold(HashSet::new(), |mut current, part| {
+                current.extend(part?);
+                Ok(current)
             })
     }
 }
@@ -192,32 +203,41 @@ fn strip_set<'i: 's, 's>(
         })
 }
 
-pub fn part_1((rules, messages): Input) -> color_eyre::Result<String> {
-    let matching = messages.iter().filter(|msg| rules.matches(msg)).count();
-    Ok(format!("Matching messages: {}", matching))
+pub fn part_1((rules, messages): Input) -> color_eyre::Result<Answer> {
+    let matches = messages
+        .iter()
+        .map(|msg| {
+            rules
+                .matches(msg)
+                .wrap_err_with(|| format!("Could not match the message {}", msg))
+        })
+        .collect::<color_eyre::Result<Vec<_>>>()?;
+    let matching = matches.into_iter().filter(|&matches| matches).count();
+    Ok(Answer::new(matching).with_description(format!("Matching messages: {}", matching)))
 }
 
-pub fn part_2((rules, messages): Input) -> color_eyre::Result<String> {
-    let rule_42 = rules.productions(42);
-    let rule_31 = rules.productions(31);
+pub fn part_2((rules, messages): Input) -> color_eyre::Result<Answer> {
+    let rule_42 = rules.productions(42)?;
+    let rule_31 = rules.productions(31)?;
 
-    let matching = messages
+    let matches = messages
         .iter()
-        .filter(|msg| rules.looping_matches(msg, &rule_42, &rule_31))
-        .count();
-    Ok(format!("Matching messages: {}", matching))
+        .map(|msg| {
+            rules
+                .looping_matches(msg, &rule_42, &rule_31)
+                .wrap_err_with(|| format!("Could not match the message {}", msg))
+        })
+        .collect::<color_eyre::Result<Vec<_>>>()?;
+    let matching = matches.into_iter().filter(|&matches| matches).count();
+    Ok(Answer::new(matching).with_description(format!("Matching messages: {}", matching)))
 }
 
 #[cfg(test)]
 mod test {
-    use super::{Input, Rules};
-    use std::collections::HashMap;
+    use super::{parse_rule, parsing, Input};
+    use crate::{DayContext, Parts};
 
     fn load_example() -> Input {
-        let mut rules = HashMap::new();
-        let mut rules_done = false;
-        let mut messages = Vec::new();
-
         let input = r#"42: 9 14 | 10 1
 9: 14 27 | 1 26
 10: 23 14 | 28 1
@@ -267,23 +287,25 @@ babaaabbbaaabaababbaabababaaab
 aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba
 "#;
 
-        input.lines().for_each(|line| {
-            super::parse_rules_messages(&mut rules, &mut rules_done, &mut messages, line).unwrap();
-        });
+        parsing(&mut DayContext::from_bytes(input, Parts::Both)).unwrap()
+    }
 
-        (Rules { rules }, messages)
+    #[test]
+    fn letter_rules() {
+        assert!(parse_rule("4: \"a\"").is_ok());
+        assert!(parse_rule("4: \"").is_err());
     }
 
     #[test]
     fn test_loopy() {
         let (rules, messages) = load_example();
 
-        let rule_42 = rules.productions(42);
-        let rule_31 = rules.productions(31);
+        let rule_42 = rules.productions(42).unwrap();
+        let rule_31 = rules.productions(31).unwrap();
 
         let matching: Vec<_> = messages
             .iter()
-            .filter(|msg| rules.looping_matches(msg, &rule_42, &rule_31))
+            .filter(|msg| rules.looping_matches(msg, &rule_42, &rule_31).unwrap())
             .collect();
         assert_eq!(
             matching,
@@ -305,50 +327,47 @@ aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba
     }
 }
 
-fn parse_rules_messages(
-    rules: &mut HashMap<u64, Vec<Rule>>,
-    rules_done: &mut bool,
-    messages: &mut Vec<String>,
-    line: &str,
-) -> color_eyre::Result<()> {
-    if !*rules_done {
-        if !line.is_empty() {
-            let (number, body) = crate::split_string_separator(line, ':')
-                .ok_or_else(|| color_eyre::eyre::eyre!("Malformed rule: {}", line))?;
-            let parts = body
-                .split("|")
-                .map(|part| -> color_eyre::Result<_> {
-                    if part.starts_with(" \"") {
-                        Ok(Rule::Letter(
-                            part.trim_start_matches(" \"").chars().nth(0).unwrap(),
-                        ))
-                    } else {
-                        let sequence = part
-                            .split_whitespace()
-                            .map(str::parse)
-                            .collect::<Result<_, _>>()?;
-                        Ok(Rule::Sequence(sequence))
-                    }
-                })
-                .collect::<Result<_, _>>()?;
-            rules.insert(number.parse()?, parts);
-        } else {
-            *rules_done = true;
-        }
-    } else {
-        messages.push(line.to_owned())
-    }
-
-    Ok(())
+fn parse_rule(line: &str) -> color_eyre::Result<(u64, Vec<Rule>)> {
+    let (number, body) = crate::split_string_separator(line, ':')
+        .ok_or_else(|| color_eyre::eyre::eyre!("Malformed rule: {}", line))?;
+    let parts = body
+        .split("|")
+        .map(|part| -> color_eyre::Result<_> {
+            if part.starts_with(" \"") {
+                let letter = part
+                    .trim_start_matches(" \"")
+                    .chars()
+                    .next()
+                    .ok_or_else(|| color_eyre::eyre::eyre!("Empty letter rule: {}", line))?;
+                Ok(Rule::Letter(letter))
+            } else {
+                let sequence = part
+                    .split_whitespace()
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()?;
+                Ok(Rule::Sequence(sequence))
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((number.parse()?, parts))
 }
 
 pub fn parsing(context: &mut DayContext) -> color_eyre::Result<Input> {
     let mut rules = HashMap::new();
-    let mut rules_done = false;
     let mut messages = Vec::new();
 
-    context.accumulate_str_lines(|_, line| {
-        parse_rules_messages(&mut rules, &mut rules_done, &mut messages, line)
+    context.accumulate_groups(|index, group| {
+        match index {
+            0 => {
+                for line in group {
+                    let (number, parts) = parse_rule(line)?;
+                    rules.insert(number, parts);
+                }
+            }
+            1 => messages = group.to_vec(),
+            _ => color_eyre::eyre::bail!("Unexpected group of lines after the messages"),
+        }
+        Ok(())
     })?;
 
     Ok((Rules { rules }, messages))
@@ -1,34 +1,45 @@
-use crate::{split_bytes_separator, DayContext};
-use bstr::{BStr, BString, ByteSlice};
-
-pub fn part_1(passwords: &[(Policy, BString)]) -> color_eyre::Result<String> {
-    let valid_count = passwords
-        .iter()
-        .filter_map(|(policy, password)| {
-            if policy.is_old_valid(password.as_ref()) {
-                Some(true)
-            } else {
-                None
-            }
-        })
-        .count();
-    Ok(format!("There are {} valid passwords", valid_count))
-}
-
-pub fn part_2(passwords: &[(Policy, BString)]) -> color_eyre::Result<String> {
-    let valid_count = passwords
-        .iter()
-        .filter_map(|(policy, password)| {
-            if policy.is_current_valid(password.as_ref()) {
-                Some(true)
-            } else {
-                None
+use crate::{split_bytes_separator, Answer, DayContext};
+use bstr::{BStr, ByteSlice};
+use regex::bytes::Regex;
+use std::{collections::HashSet, fmt::Display};
+
+type Input<'i, 'p> = &'p [(Policy, &'i BStr)];
+
+/// Counts the passwords allowed by the policy, and explains the others when `explain` is set
+fn count_valid<P: PasswordPolicy>(
+    passwords: Input,
+    rule: &P,
+    explain: bool,
+) -> color_eyre::Result<Answer> {
+    let mut valid_count = 0;
+    let mut violations = Vec::new();
+    for (line, (policy, password)) in passwords.iter().enumerate() {
+        match rule.check(policy, password) {
+            Ok(()) => valid_count += 1,
+            Err(violation) if explain => {
+                violations.push(format!("Line {} ({}): {}", line + 1, password, violation))
             }
-        })
-        .count();
-    Ok(format!("There are {} valid passwords", valid_count))
+            Err(_) => (),
+        }
+    }
+
+    let mut description = format!("There are {} valid passwords", valid_count);
+    for violation in violations {
+        description.push_str("\n  - ");
+        description.push_str(&violation);
+    }
+    Ok(Answer::new(valid_count).with_description(description))
 }
 
+pub fn part_1(passwords: Input) -> color_eyre::Result<Answer> {
+    count_valid(passwords, &OldPolicy, false)
+}
+
+pub fn part_2(passwords: Input) -> color_eyre::Result<Answer> {
+    count_valid(passwords, &CurrentPolicy, false)
+}
+
+/// Policy written next to a password: two numbers and a letter
 pub struct Policy {
     a: usize,
     b: usize,
@@ -36,15 +47,6 @@ pub struct Policy {
 }
 
 impl Policy {
-    fn is_old_valid(&self, password: &BStr) -> bool {
-        (self.a..(self.b + 1)).contains(&password.iter().filter(|&&l| l == self.letter).count())
-    }
-
-    fn is_current_valid(&self, password: &BStr) -> bool {
-        let ascii_chars = password.as_bytes();
-        (ascii_chars[self.a] == self.letter as u8) ^ (ascii_chars[self.b] == self.letter as u8)
-    }
-
     fn parse(inp: &BStr) -> color_eyre::Result<Self> {
         let (range, letter) = split_bytes_separator(inp, b' ')
             .ok_or_else(|| color_eyre::eyre::eyre!("Missing ' '"))?;
@@ -65,16 +67,254 @@ impl Policy {
     }
 }
 
-pub fn parsing(ctx: &mut DayContext) -> color_eyre::Result<Vec<(Policy, BString)>> {
-    let lines = ctx.parse_byte_lines(|line| -> color_eyre::eyre::Result<_> {
+/// Why a password is not allowed by a policy
+#[derive(Debug, PartialEq, Eq)]
+pub struct Violation(String);
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Way of interpreting the policy written next to each password
+pub trait PasswordPolicy {
+    fn check(&self, policy: &Policy, password: &BStr) -> Result<(), Violation>;
+}
+
+/// The letter appears between `a` and `b` times
+pub struct OldPolicy;
+
+impl PasswordPolicy for OldPolicy {
+    fn check(&self, policy: &Policy, password: &BStr) -> Result<(), Violation> {
+        let count = password.iter().filter(|&&l| l == policy.letter).count();
+        if (policy.a..=policy.b).contains(&count) {
+            Ok(())
+        } else {
+            Err(Violation(format!(
+                "'{}' appears {} times, expected {} to {}",
+                policy.letter as char, count, policy.a, policy.b
+            )))
+        }
+    }
+}
+
+/// The letter is at exactly one of the positions `a` and `b`, starting at 1
+pub struct CurrentPolicy;
+
+impl PasswordPolicy for CurrentPolicy {
+    fn check(&self, policy: &Policy, password: &BStr) -> Result<(), Violation> {
+        // A position past the end of the password does not hold the letter
+        let at = |position: usize| {
+            position
+                .checked_sub(1)
+                .map(|i| password.get(i).is_some_and(|&l| l == policy.letter))
+                .ok_or_else(|| Violation("Positions start at 1, not 0".into()))
+        };
+
+        match (at(policy.a)?, at(policy.b)?) {
+            (true, false) | (false, true) => Ok(()),
+            (true, true) => Err(Violation(format!(
+                "'{}' is at both positions {} and {}",
+                policy.letter as char, policy.a, policy.b
+            ))),
+            (false, false) => Err(Violation(format!(
+                "'{}' is at neither position {} nor {}",
+                policy.letter as char, policy.a, policy.b
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Comparison {
+    AtLeast,
+    AtMost,
+    Exactly,
+}
+
+impl Comparison {
+    fn holds(self, value: usize, bound: usize) -> bool {
+        match self {
+            Comparison::AtLeast => value >= bound,
+            Comparison::AtMost => value <= bound,
+            Comparison::Exactly => value == bound,
+        }
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Comparison::AtLeast => write!(f, "at least"),
+            Comparison::AtMost => write!(f, "at most"),
+            Comparison::Exactly => write!(f, "exactly"),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Clause {
+    Count,
+    Positions,
+    Distinct(Comparison, usize),
+    Length(Comparison, usize),
+    Matches(Regex),
+}
+
+/// Policy made of clauses separated by `;`, which all need to hold:
+///
+/// - `count`: the letter appears between `a` and `b` times
+/// - `positions`: the letter is at exactly one of the positions `a` and `b`
+/// - `distinct >= N`, `length <= N`, `distinct = N`…: bounds on the number of distinct
+///   letters or on the length of the password
+/// - `matches REGEX`: the password matches the regex
+#[derive(Debug)]
+pub struct PolicyExpression {
+    clauses: Vec<Clause>,
+}
+
+impl std::str::FromStr for PolicyExpression {
+    type Err = color_eyre::eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let clauses = s
+            .split(';')
+            .map(str::trim)
+            .map(|clause| {
+                let (name, argument) = match clause.find(' ') {
+                    Some(space) => (&clause[..space], clause[space..].trim_start()),
+                    None => (clause, ""),
+                };
+                let bound = || -> color_eyre::Result<_> {
+                    let (comparison, bound) = if let Some(bound) = argument.strip_prefix(">=") {
+                        (Comparison::AtLeast, bound)
+                    } else if let Some(bound) = argument.strip_prefix("<=") {
+                        (Comparison::AtMost, bound)
+                    } else if let Some(bound) = argument.strip_prefix('=') {
+                        (Comparison::Exactly, bound)
+                    } else {
+                        color_eyre::eyre::bail!("Expected >=, <= or = in the clause {:?}", clause)
+                    };
+                    Ok((comparison, bound.trim().parse()?))
+                };
+
+                match name {
+                    "count" if argument.is_empty() => Ok(Clause::Count),
+                    "positions" if argument.is_empty() => Ok(Clause::Positions),
+                    "distinct" => bound().map(|(c, n)| Clause::Distinct(c, n)),
+                    "length" => bound().map(|(c, n)| Clause::Length(c, n)),
+                    "matches" => Ok(Clause::Matches(Regex::new(argument)?)),
+                    _ => color_eyre::eyre::bail!("Unknown policy clause {:?}", clause),
+                }
+            })
+            .collect::<color_eyre::Result<_>>()?;
+
+        Ok(Self { clauses })
+    }
+}
+
+impl PasswordPolicy for PolicyExpression {
+    fn check(&self, policy: &Policy, password: &BStr) -> Result<(), Violation> {
+        for clause in &self.clauses {
+            match clause {
+                Clause::Count => OldPolicy.check(policy, password)?,
+                Clause::Positions => CurrentPolicy.check(policy, password)?,
+                &Clause::Distinct(comparison, bound) => {
+                    let distinct = password.iter().collect::<HashSet<_>>().len();
+                    if !comparison.holds(distinct, bound) {
+                        return Err(Violation(format!(
+                            "Has {} distinct letters, expected {} {}",
+                            distinct, comparison, bound
+                        )));
+                    }
+                }
+                &Clause::Length(comparison, bound) => {
+                    if !comparison.holds(password.len(), bound) {
+                        return Err(Violation(format!(
+                            "Has {} letters, expected {} {}",
+                            password.len(),
+                            comparison,
+                            bound
+                        )));
+                    }
+                }
+                Clause::Matches(regex) => {
+                    if !regex.is_match(password) {
+                        return Err(Violation(format!("Does not match /{}/", regex)));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn parsing<'i>(
+    ctx: &mut DayContext,
+    input: &'i [u8],
+) -> color_eyre::Result<Vec<(Policy, &'i BStr)>> {
+    ctx.parse_borrowed_lines(input, |line| {
         let (policy, password) = split_bytes_separator(line, b':')
             .ok_or_else(|| color_eyre::eyre::eyre!("Colud not split password at ':'"))?;
-        Ok((Policy::parse(policy.as_ref())?, BString::from(password)))
-    })?;
-    Ok(lines)
+        Ok((
+            Policy::parse(policy.as_ref())?,
+            password.trim_start().as_bstr(),
+        ))
+    })
 }
 
 pub fn execute(context: &mut DayContext) -> color_eyre::Result<()> {
-    let passwords = parsing(context)?;
-    context.execute(passwords.as_ref(), part_1, part_2)
+    let input = context.take_input_bytes()?;
+    let passwords = parsing(context, &input)?;
+
+    let explain = context.options().explain;
+    match &context.options().policy {
+        Some(expression) => {
+            let policy: PolicyExpression = expression.parse()?;
+            context.execute(
+                passwords.as_ref(),
+                |passwords| count_valid(passwords, &policy, explain),
+                |passwords| count_valid(passwords, &policy, explain),
+            )
+        }
+        None => context.execute(
+            passwords.as_ref(),
+            |passwords| count_valid(passwords, &OldPolicy, explain),
+            |passwords| count_valid(passwords, &CurrentPolicy, explain),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CurrentPolicy, OldPolicy, PasswordPolicy, Policy, PolicyExpression};
+    use bstr::ByteSlice;
+
+    #[test]
+    fn policies() {
+        let policy = Policy::parse(b"1-3 a".as_bstr()).unwrap();
+        assert!(OldPolicy.check(&policy, b"abcde".as_bstr()).is_ok());
+        assert!(CurrentPolicy.check(&policy, b"abcde".as_bstr()).is_ok());
+        assert!(CurrentPolicy.check(&policy, b"ab".as_bstr()).is_ok());
+        assert_eq!(
+            CurrentPolicy
+                .check(&Policy::parse(b"0-2 a".as_bstr()).unwrap(), b"ab".as_bstr())
+                .unwrap_err()
+                .to_string(),
+            "Positions start at 1, not 0"
+        );
+
+        let expression: PolicyExpression = "count; distinct >= 4; matches ^a".parse().unwrap();
+        assert!(expression.check(&policy, b"abcde".as_bstr()).is_ok());
+        assert_eq!(
+            expression
+                .check(&policy, b"aabab".as_bstr())
+                .unwrap_err()
+                .to_string(),
+            "Has 2 distinct letters, expected at least 4"
+        );
+        assert!("distinct 4".parse::<PolicyExpression>().is_err());
+    }
 }
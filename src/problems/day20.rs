@@ -1,20 +1,23 @@
-use crate::DayContext;
+use crate::{Answer, DayContext, Grid};
 use std::collections::{HashMap, HashSet};
 
 type Input = Vec<Tile>;
 
-pub fn part_1(tiles: Input) -> color_eyre::Result<String> {
-    let corners: u64 = get_corners(&tiles)
+pub fn part_1(tiles: Input) -> color_eyre::Result<Answer> {
+    let corners: u64 = get_corners(&tiles)?
         .corners
         .keys()
         .map(|&TileId(id)| id as u64)
         .product();
-    Ok(format!("Values at the corners: {}", corners))
+    Ok(Answer::new(corners).with_description(format!("Values at the corners: {}", corners)))
 }
 
-pub fn part_2(tiles: Input) -> color_eyre::Result<String> {
-    let _remade = remake(&tiles);
-    todo!()
+pub fn part_2(tiles: Input) -> color_eyre::Result<Answer> {
+    let layout = remake(&tiles)?;
+    let image = render(&tiles, &layout);
+    let roughness = water_roughness(&image)
+        .ok_or_else(|| color_eyre::eyre::eyre!("No sea monster was found in the image"))?;
+    Ok(Answer::new(roughness).with_description(format!("Water roughness: {}", roughness)))
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -25,28 +28,6 @@ enum Direction {
     West,
 }
 
-impl Direction {
-    fn same_axis(&self, other: Direction) -> bool {
-        match self {
-            Direction::North | Direction::South => {
-                other == Direction::North || other == Direction::South
-            }
-            Direction::East | Direction::West => {
-                other == Direction::East || other == Direction::West
-            }
-        }
-    }
-
-    fn flip(&self) -> Direction {
-        match self {
-            Direction::North => Direction::South,
-            Direction::South => Direction::North,
-            Direction::East => Direction::West,
-            Direction::West => Direction::East,
-        }
-    }
-}
-
 #[derive(Clone, Copy, Debug)]
 enum Action {
     Flip { axis: Direction },
@@ -54,6 +35,53 @@ enum Action {
     CounterClockwise,
 }
 
+/// Actions leading to each of the 8 orientations of a square
+const ORIENTATIONS: [&[Action]; 8] = [
+    &[],
+    &[Action::Clockwise],
+    &[Action::Clockwise, Action::Clockwise],
+    &[Action::CounterClockwise],
+    &[Action::Flip {
+        axis: Direction::North,
+    }],
+    &[
+        Action::Flip {
+            axis: Direction::North,
+        },
+        Action::Clockwise,
+    ],
+    &[
+        Action::Flip {
+            axis: Direction::North,
+        },
+        Action::Clockwise,
+        Action::Clockwise,
+    ],
+    &[
+        Action::Flip {
+            axis: Direction::North,
+        },
+        Action::CounterClockwise,
+    ],
+];
+
+/// Applies the actions to an image, one after the other
+fn orient(image: &Grid<bool>, actions: &[Action]) -> Grid<bool> {
+    actions
+        .iter()
+        .fold(image.clone(), |image, &action| match action {
+            Action::Clockwise => image.rotate_clockwise(),
+            Action::CounterClockwise => image.rotate_counterclockwise(),
+            Action::Flip {
+                axis: Direction::North,
+            }
+            | Action::Flip {
+                axis: Direction::South,
+            } => image.flip_horizontal(),
+            Action::Flip { .. } => image.flip_vertical(),
+        })
+}
+
 #[derive(Debug, Clone, Copy)]
 struct Borders {
     north: u16,
@@ -67,45 +95,21 @@ impl Borders {
         [self.north, self.south, self.east, self.west]
     }
 
-    // Which border is it, and flipped?
-    fn get_direction(&self, border: u16, tile_size: u8) -> Option<(Direction, bool)> {
-        let flipped = flip(border, tile_size);
-
-        if self.north == border {
-            Some((Direction::North, false))
-        } else if self.north == flipped {
-            Some((Direction::North, true))
-        } else if self.south == border {
-            Some((Direction::South, false))
-        } else if self.south == flipped {
-            Some((Direction::South, true))
-        } else if self.east == border {
-            Some((Direction::East, false))
-        } else if self.east == flipped {
-            Some((Direction::East, true))
-        } else if self.west == border {
-            Some((Direction::West, false))
-        } else if self.west == flipped {
-            Some((Direction::West, true))
-        } else {
-            None
-        }
-    }
-
-    fn rotate(&self, clockwise: bool) -> Borders {
+    // Borders are read left to right and top to bottom, so some of them are reversed
+    fn rotate(&self, clockwise: bool, tile_size: u8) -> Borders {
         if clockwise {
             Borders {
-                north: self.west,
+                north: flip(self.west, tile_size),
                 east: self.north,
-                south: self.east,
+                south: flip(self.east, tile_size),
                 west: self.south,
             }
         } else {
             Borders {
                 north: self.east,
-                west: self.north,
+                west: flip(self.north, tile_size),
                 south: self.west,
-                east: self.south,
+                east: flip(self.south, tile_size),
             }
         }
     }
@@ -128,183 +132,36 @@ impl Borders {
         }
     }
 
-    fn flip_fit(
-        &self,
-        a: u16,
-        b: u16,
-        tile_size: u8,
-    ) -> (Direction, Direction, Borders, Vec<Action>) {
-        let mut corner_actions = Vec::new();
-        let mut fitted = self.clone();
-
-        let (mut direction_a, flipped_a) = self.get_direction(a, tile_size).unwrap();
-        if flipped_a {
-            fitted = fitted.flip(direction_a, tile_size);
-            corner_actions.push(Action::Flip { axis: direction_a });
-        }
-
-        let (direction_b, flipped_b) = fitted.get_direction(b, tile_size).unwrap();
-        assert!(!direction_a.same_axis(direction_b));
-        if flipped_b {
-            fitted = fitted.flip(direction_b, tile_size);
-            corner_actions.push(Action::Flip { axis: direction_b });
-            direction_a = direction_a.flip();
+    fn apply(&self, action: Action, tile_size: u8) -> Borders {
+        match action {
+            Action::Clockwise => self.rotate(true, tile_size),
+            Action::CounterClockwise => self.rotate(false, tile_size),
+            Action::Flip { axis } => self.flip(axis, tile_size),
         }
-
-        (direction_a, direction_b, fitted, corner_actions)
-    }
-
-    fn fit_north_west(&self, a: u16, b: u16, tile_size: u8) -> (Borders, Vec<Action>) {
-        let (direction_a, direction_b, mut fitted, mut corner_actions) =
-            self.flip_fit(a, b, tile_size);
-
-        // Now we have the piece correctly flipped, we just need to rotate it in place
-        match direction_a {
-            Direction::East => match direction_b {
-                Direction::North => {
-                    corner_actions.push(Action::CounterClockwise);
-                    fitted = fitted.rotate(false);
-                }
-                Direction::South => {
-                    corner_actions.push(Action::CounterClockwise);
-                    fitted = fitted.rotate(false);
-                    corner_actions.push(Action::CounterClockwise);
-                    fitted = fitted.rotate(false);
-                }
-                _ => unreachable!(),
-            },
-            Direction::West => match direction_b {
-                Direction::North => (),
-                Direction::South => {
-                    corner_actions.push(Action::Clockwise);
-                    fitted = fitted.rotate(true);
-                }
-                _ => unreachable!(),
-            },
-            Direction::South => match direction_b {
-                Direction::East => {
-                    corner_actions.push(Action::CounterClockwise);
-                    fitted = fitted.rotate(false);
-                    corner_actions.push(Action::CounterClockwise);
-                    fitted = fitted.rotate(false);
-                }
-                Direction::West => {
-                    corner_actions.push(Action::Clockwise);
-                    fitted = fitted.rotate(true);
-                }
-                _ => unreachable!(),
-            },
-            Direction::North => match direction_b {
-                Direction::East => {
-                    corner_actions.push(Action::CounterClockwise);
-                    fitted = fitted.rotate(false);
-                }
-                Direction::West => (),
-                _ => unreachable!(),
-            },
-        };
-
-        (fitted, corner_actions)
     }
 
-    fn fit_north_east(&self, a: u16, b: u16, tile_size: u8) -> (Borders, Vec<Action>) {
-        let (direction_a, direction_b, mut fitted, mut corner_actions) =
-            self.flip_fit(a, b, tile_size);
-
-        // Now we have the piece correctly flipped, we just need to rotate it in place
-        match direction_a {
-            Direction::East => match direction_b {
-                Direction::North => (),
-                Direction::South => {
-                    corner_actions.push(Action::CounterClockwise);
-                    fitted = fitted.rotate(false);
-                }
-                _ => unreachable!(),
-            },
-            Direction::West => match direction_b {
-                Direction::North => {
-                    corner_actions.push(Action::Clockwise);
-                    fitted = fitted.rotate(true);
-                },
-                Direction::South => {
-                    corner_actions.push(Action::CounterClockwise);
-                    fitted = fitted.rotate(false);
-                    corner_actions.push(Action::CounterClockwise);
-                    fitted = fitted.rotate(false);
-                }
-                _ => unreachable!(),
-            },
-            Direction::South => match direction_b {
-                Direction::East => {
-                    corner_actions.push(Action::CounterClockwise);
-                    fitted = fitted.rotate(false);
-                }
-                Direction::West => {
-                    corner_actions.push(Action::CounterClockwise);
-                    fitted = fitted.rotate(false);
-                    corner_actions.push(Action::CounterClockwise);
-                    fitted = fitted.rotate(false);
-                }
-                _ => unreachable!(),
-            },
-            Direction::North => match direction_b {
-                Direction::East => {
-                    ()
-                }
-                Direction::West => {
-                    corner_actions.push(Action::Clockwise);
-                    fitted = fitted.rotate(true);
-                },
-                _ => unreachable!(),
-            },
-        };
-
-        (fitted, corner_actions)
-    }
-
-    fn try_fit(&self, north: u16, west: u16, tile_size: u8) -> Option<(Borders, Vec<Action>)> {
-        let (direction_north, direction_west, mut fitted, mut corner_actions) =
-            self.flip_fit(north, west, tile_size);
-
-        match direction_north {
-            Direction::North => {
-                if direction_west == Direction::West {
-                    return None;
-                }
-            }
-            Direction::West => {
-                if direction_west != Direction::South {
-                    return None;
-                }
-                corner_actions.push(Action::Clockwise);
-                fitted = fitted.rotate(true);
-            }
-            Direction::East => {
-                if direction_west == Direction::North {
-                    return None;
-                }
-                corner_actions.push(Action::CounterClockwise);
-                fitted = fitted.rotate(false);
-            }
-            Direction::South => {
-                if direction_west == Direction::East {
-                    return None;
-                }
-                corner_actions.push(Action::CounterClockwise);
-                fitted = fitted.rotate(false);
-                corner_actions.push(Action::CounterClockwise);
-                fitted = fitted.rotate(false);
+    /// Finds an orientation accepted by `fits`, and the actions leading to it
+    fn try_fit<F>(&self, tile_size: u8, fits: F) -> Option<(Borders, Vec<Action>)>
+    where
+        F: Fn(&Borders) -> bool,
+    {
+        ORIENTATIONS.iter().find_map(|actions| {
+            let fitted = actions
+                .iter()
+                .fold(*self, |borders, &action| borders.apply(action, tile_size));
+            if fits(&fitted) {
+                Some((fitted, actions.to_vec()))
+            } else {
+                None
             }
-        }
-
-        Some((fitted, corner_actions))
+        })
     }
 }
 
 #[derive(Clone)]
 pub struct Tile {
     id: TileId,
-    tile: Vec<Vec<bool>>,
+    tile: Grid<bool>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -319,134 +176,184 @@ fn flip(border: u16, size: u8) -> u16 {
     border.reverse_bits() >> (16 - size)
 }
 
+/// Size of the tiles, which need to be squares of the same size that fits in a border
+fn tile_size(tiles: &[Tile]) -> color_eyre::Result<u8> {
+    let first = tiles
+        .first()
+        .ok_or_else(|| color_eyre::eyre::eyre!("There are no tiles"))?;
+    let size = first.tile.width();
+    // Borders are encoded in a u16, and the image needs tiles with an inside
+    if !(2..=16).contains(&size) {
+        color_eyre::eyre::bail!("Tiles need between 2 and 16 cells per side, not {}", size);
+    }
+
+    match tiles
+        .iter()
+        .find(|tile| tile.tile.width() != size || tile.tile.height() != size)
+    {
+        Some(tile) => color_eyre::eyre::bail!(
+            "Tile {} is {}x{}, expected {}x{}",
+            tile.id.0,
+            tile.tile.width(),
+            tile.tile.height(),
+            size,
+            size
+        ),
+        None => Ok(size as u8),
+    }
+}
+
 struct CornerLookup {
     borders: HashMap<TileId, Borders>,
     possible_joins: HashMap<u16, HashSet<TileId>>,
     corners: HashMap<TileId, HashSet<u16>>,
-    border_tiles: HashMap<TileId, HashSet<u16>>,
 }
 
-fn remake(tiles: &[Tile]) -> Vec<Vec<(TileId, Vec<Action>)>> {
-    let tile_size = tiles[0].tile.len() as u8;
+/// The tile sharing `border` with `tile`
+fn joined(
+    possible_joins: &HashMap<u16, HashSet<TileId>>,
+    border: u16,
+    tile: TileId,
+) -> color_eyre::Result<TileId> {
+    possible_joins
+        .get(&border)
+        .and_then(|tiles| tiles.iter().find(|&&other| other != tile))
+        .copied()
+        .ok_or_else(|| color_eyre::eyre::eyre!("No tile joins tile {} on {:b}", tile.0, border))
+}
+
+/// Tiles of the image, row by row, with the actions orienting them
+type Layout = Vec<Vec<(TileId, Vec<Action>)>>;
+
+fn remake(tiles: &[Tile]) -> color_eyre::Result<Layout> {
+    let tile_size = tile_size(tiles)?;
     let CornerLookup {
         corners,
         borders,
         possible_joins,
-        border_tiles,
-    } = get_corners(tiles);
-
-    let mut joined_tiles = Vec::new();
+    } = get_corners(tiles)?;
 
-    let tiles_per_row = (tiles.len() as f64).sqrt() as usize;
-    let mut first_row_picker = |index, tile_id| {
-        if index < tiles_per_row - 1 {
-            let tile_border = border_tiles.get(&tile_id).unwrap();
-            assert_eq!(tile_border.len(), 1);
-            *tile_border.into_iter().nth(0).unwrap()
-        } else {
-            let corner_borders = *borders.get(&tile_id).unwrap();
-            let mut outside_borders = dbg!(border_tiles.get(&tile_id).unwrap()).iter();
-
-            let (&border_a, &border_b) = (
-                outside_borders.next().unwrap(),
-                outside_borders.next().unwrap(),
-            );
-
-            dbg!(corner_borders);
-            let (corner_borders, _) = corner_borders.fit_north_east(border_a, border_b, tile_size);
-            corner_borders.north
-        }
+    // Borders matching no other tile are on the outside of the image
+    let fits = |expected: Option<u16>, border: u16| match expected {
+        Some(expected) => expected == border,
+        None => possible_joins[&border].len() == 1,
     };
 
-    for (corner, outside_borders) in corners {
-        let mut outside_borders = outside_borders.into_iter();
-        let (border_a, border_b) = (
-            outside_borders.next().unwrap(),
-            outside_borders.next().unwrap(),
-        );
-
-        let corner_borders = *borders.get(&corner).unwrap();
-        let (corner_borders, corner_actions) =
-            corner_borders.fit_north_west(border_a, border_b, tile_size);
-
-        let mut row = vec![(corner, corner_borders, corner_actions)];
-        if place_tile(&mut first_row_picker, &mut row, &possible_joins, &borders, tile_size) {
-            joined_tiles.push(row);
-            break;
-        }
+    let tiles_per_row = (tiles.len() as f64).sqrt().round() as usize;
+    if tiles_per_row * tiles_per_row != tiles.len() {
+        color_eyre::eyre::bail!("{} tiles do not make a square image", tiles.len());
     }
-    if joined_tiles.len() == 0 {
-        panic!("Did not find a correct corner")
+    let mut joined_tiles: Vec<Vec<(TileId, Borders, Vec<Action>)>> = Vec::new();
+    for row in 0..tiles_per_row {
+        let mut current_row: Vec<(TileId, Borders, Vec<Action>)> = Vec::new();
+        while current_row.len() < tiles_per_row {
+            let column = current_row.len();
+            let above = joined_tiles.last().map(|previous| &previous[column]);
+            let before = current_row.last();
+
+            let tile = match (before, above) {
+                (Some((id, borders, _)), _) => joined(&possible_joins, borders.east, *id)?,
+                (None, Some((id, borders, _))) => joined(&possible_joins, borders.south, *id)?,
+                (None, None) => *corners
+                    .keys()
+                    .next()
+                    .ok_or_else(|| color_eyre::eyre::eyre!("Did not find a correct corner"))?,
+            };
+            let north = above.map(|(_, borders, _)| borders.south);
+            let west = before.map(|(_, borders, _)| borders.east);
+
+            let (fitted, actions) = borders[&tile]
+                .try_fit(tile_size, |b| fits(north, b.north) && fits(west, b.west))
+                .ok_or_else(|| {
+                    color_eyre::eyre::eyre!("Tile {} does not fit in ({}, {})", tile.0, row, column)
+                })?;
+            current_row.push((tile, fitted, actions));
+        }
+        joined_tiles.push(current_row);
     }
 
-    fill_row(
-        first_row_picker,
-        &mut joined_tiles[0],
-        tiles_per_row,
-        &possible_joins,
-        &borders,
-        tile_size,
-    );
+    Ok(joined_tiles
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|(id, _, actions)| (id, actions))
+                .collect()
+        })
+        .collect())
+}
 
-    dbg!(&joined_tiles);
+/// Orients the tiles, strips their borders and joins them in a single image
+fn render(tiles: &[Tile], layout: &Layout) -> Grid<bool> {
+    let tiles: HashMap<TileId, &Tile> = tiles.iter().map(|tile| (tile.id, tile)).collect();
+    let inner = layout
+        .first()
+        .and_then(|row| row.first())
+        .map_or(0, |(id, _)| tiles[id].tile.width() - 2);
+
+    let width = layout.first().map_or(0, Vec::len) * inner;
+    let mut image = Grid::filled(width, layout.len() * inner, false);
+    for (row, tiles_row) in layout.iter().enumerate() {
+        for (column, (id, actions)) in tiles_row.iter().enumerate() {
+            let oriented = orient(&tiles[id].tile, actions);
+            for y in 0..inner {
+                for x in 0..inner {
+                    image[(column * inner + x, row * inner + y)] = oriented[(x + 1, y + 1)];
+                }
+            }
+        }
+    }
 
-    todo!()
+    image
 }
 
-fn place_tile<F: FnMut(usize, TileId) -> u16>(
-    north_picker: &mut F,
-    current_row: &mut Vec<(TileId, Borders, Vec<Action>)>,
-    possible_joins: &HashMap<u16, HashSet<TileId>>,
-    borders: &HashMap<TileId, Borders>,
-    tile_size: u8,
-) -> bool {
-    let (prev_id, prev_borders) = current_row
-        .last()
-        .map(|(id, borders, _)| (id, borders))
-        .unwrap();
-    let possible = possible_joins.get(&prev_borders.east).unwrap();
-    assert_eq!(possible.len(), 2);
-
-    let &linked = possible.iter().filter(|&x| x != prev_id).nth(0).unwrap();
-    dbg!(prev_id, linked);
-
-    let north = north_picker(current_row.len(), linked);
-    let (fitted, actions) =
-        match borders
-            .get(&linked)
-            .unwrap()
-            .try_fit(north, prev_borders.east, tile_size)
-        {
-            Some(x) => x,
-            None => return false,
-        };
-    current_row.push((linked, fitted, actions));
-    true
-}
+const SEA_MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
 
-fn fill_row<F: FnMut(usize, TileId) -> u16>(
-    mut north_picker: F,
-    current_row: &mut Vec<(TileId, Borders, Vec<Action>)>,
-    tiles_per_row: usize,
-    possible_joins: &HashMap<u16, HashSet<TileId>>,
-    borders: &HashMap<TileId, Borders>,
-    tile_size: u8,
-) {
-    while current_row.len() < tiles_per_row {
-        assert!(place_tile(
-            &mut north_picker,
-            current_row,
-            possible_joins,
-            borders,
-            tile_size,
-        ));
-    }
+/// Counts the `#` that are not part of a sea monster, in the orientation showing them
+fn water_roughness(image: &Grid<bool>) -> Option<usize> {
+    let monster: Vec<(usize, usize)> = SEA_MONSTER
+        .iter()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.bytes()
+                .enumerate()
+                .filter(|&(_, c)| c == b'#')
+                .map(move |(x, _)| (x, y))
+        })
+        .collect();
+    let (height, width) = (SEA_MONSTER.len(), SEA_MONSTER[0].len());
+
+    ORIENTATIONS.iter().find_map(|actions| {
+        let image = orient(image, actions);
+
+        let mut monster_cells = HashSet::new();
+        for y in 0..=image.height().saturating_sub(height) {
+            for x in 0..=image.width().saturating_sub(width) {
+                if monster
+                    .iter()
+                    .all(|&(dx, dy)| image.get(x + dx, y + dy) == Some(&true))
+                {
+                    monster_cells.extend(monster.iter().map(|&(dx, dy)| (x + dx, y + dy)));
+                }
+            }
+        }
+
+        if monster_cells.is_empty() {
+            None
+        } else {
+            let rough = image.iter().filter(|&(_, &px)| px).count();
+            Some(rough - monster_cells.len())
+        }
+    })
 }
 
-fn get_corners(tiles: &[Tile]) -> CornerLookup {
+fn get_corners(tiles: &[Tile]) -> color_eyre::Result<CornerLookup> {
+    let tile_size = tile_size(tiles)?;
     let borders: HashMap<TileId, Borders> =
         tiles.iter().map(|tile| (tile.id, tile.borders())).collect();
-    let tile_size = tiles[0].tile.len() as u8;
 
     let mut possible_joins = HashMap::new();
     for (&tile, borders) in &borders {
@@ -475,7 +382,7 @@ fn get_corners(tiles: &[Tile]) -> CornerLookup {
             tiles
         });
 
-    CornerLookup {
+    Ok(CornerLookup {
         corners: possible_border
             .iter()
             .filter(|(_, borders)| borders.len() > 1)
@@ -483,21 +390,17 @@ fn get_corners(tiles: &[Tile]) -> CornerLookup {
             .collect(),
         borders,
         possible_joins,
-        border_tiles: possible_border,
-    }
+    })
 }
 
 impl Tile {
     fn border(&self, direction: Direction) -> Vec<bool> {
+        let (width, height) = (self.tile.width(), self.tile.height());
         match direction {
-            Direction::North => self.tile.first().unwrap().clone(),
-            Direction::South => self.tile.last().unwrap().clone(),
-            Direction::West => self
-                .tile
-                .iter()
-                .map(|line| *line.first().unwrap())
-                .collect(),
-            Direction::East => self.tile.iter().map(|line| *line.last().unwrap()).collect(),
+            Direction::North => (0..width).map(|x| self.tile[(x, 0)]).collect(),
+            Direction::South => (0..width).map(|x| self.tile[(x, height - 1)]).collect(),
+            Direction::West => (0..height).map(|y| self.tile[(0, y)]).collect(),
+            Direction::East => (0..height).map(|y| self.tile[(width - 1, y)]).collect(),
         }
     }
     fn borders(&self) -> Borders {
@@ -513,54 +416,42 @@ impl Tile {
 impl std::fmt::Debug for Tile {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "id = {}", self.id.0)?;
-        for line in &self.tile {
-            for px in line {
-                write!(f, "{}", if *px { "#" } else { "." })?;
-            }
-            writeln!(f)?;
-        }
-
-        Ok(())
+        write!(f, "{}", self.tile.map(|&px| if px { '#' } else { '.' }))
     }
 }
 
-fn parse_tile_line(
-    tiles: &mut Vec<Tile>,
-    current_tile: &mut Vec<Vec<bool>>,
-    current_id: &mut Option<u16>,
-    line: &str,
-) -> color_eyre::Result<()> {
-    if !line.is_empty() {
-        if let Some(id) = line.strip_prefix("Tile ") {
-            let id = id.trim_end_matches(":");
-            if let Some(x) = current_id {
-                let mut new_current = Vec::new();
-                std::mem::swap(&mut new_current, current_tile);
-                tiles.push(Tile {
-                    id: TileId(*x),
-                    tile: new_current,
-                });
-            }
-            *current_id = Some(id.parse()?);
-        } else {
-            current_tile.push(
-                line.as_bytes()
-                    .iter()
-                    .map(|c| match c {
-                        b'#' => true,
-                        b'.' => false,
-                        _ => panic!("Invalid char in image"),
-                    })
-                    .collect(),
-            )
-        }
-    }
-    Ok(())
+fn parse_tile(lines: &[String]) -> color_eyre::Result<Tile> {
+    let (header, rows) = lines
+        .split_first()
+        .ok_or_else(|| color_eyre::eyre::eyre!("Empty tile"))?;
+    let id = header
+        .strip_prefix("Tile ")
+        .and_then(|id| id.strip_suffix(':'))
+        .ok_or_else(|| color_eyre::eyre::eyre!("Malformed tile header: {}", header))?;
+
+    let tile = rows
+        .iter()
+        .map(|row| {
+            row.bytes()
+                .map(|c| match c {
+                    b'#' => Ok(true),
+                    b'.' => Ok(false),
+                    _ => color_eyre::eyre::bail!("Invalid char in image: {}", c as char),
+                })
+                .collect()
+        })
+        .collect::<color_eyre::Result<_>>()?;
+
+    Ok(Tile {
+        id: TileId(id.parse()?),
+        tile: Grid::from_rows(tile)?,
+    })
 }
 
 #[cfg(test)]
 mod test {
-    use super::{get_corners, parse_tile_line, Tile};
+    use super::{get_corners, parsing, remake, Tile};
+    use crate::{DayContext, Parts};
 
     #[test]
     fn encode() {
@@ -576,6 +467,7 @@ mod test {
     fn corners_test() {
         let tiles = load_example();
         let corners: u64 = get_corners(&tiles)
+            .unwrap()
             .corners
             .keys()
             .map(|&super::TileId(id)| id as u64)
@@ -584,13 +476,40 @@ mod test {
         assert_eq!(corners, 20899048083289)
     }
 
-    /*
     #[test]
-    fn remake_test() {
-        let tiles = load_example();
-        let _ = super::remake(&tiles);
-        todo!()
-    }*/
+    fn orientations() {
+        let tile = &load_example()[0];
+        let tile_size = tile.tile.width() as u8;
+        for actions in super::ORIENTATIONS.iter() {
+            let oriented = Tile {
+                id: tile.id,
+                tile: super::orient(&tile.tile, actions),
+            };
+            let borders = actions
+                .iter()
+                .fold(tile.borders(), |b, &a| b.apply(a, tile_size));
+            assert_eq!(oriented.borders().as_slice(), borders.as_slice());
+        }
+    }
+
+    #[test]
+    fn water_roughness() {
+        let answer = super::part_2(load_example()).unwrap();
+        assert_eq!(answer.value(), "273");
+    }
+
+    #[test]
+    fn malformed_tiles() {
+        assert!(remake(&[]).is_err());
+
+        let mut tiles = load_example();
+        tiles.pop();
+        assert!(remake(&tiles).is_err());
+
+        let wide = "Tile 1:\n#################\n#################\n";
+        let tiles = parsing(&mut DayContext::from_bytes(wide, Parts::Both)).unwrap();
+        assert!(get_corners(&tiles).is_err());
+    }
 
     fn load_example() -> Vec<Tile> {
         let input = r#"Tile 2311:
@@ -702,38 +621,12 @@ Tile 3079:
 ..#.###...
 "#;
 
-        let mut tiles = Vec::new();
-        let mut current_tile = Vec::new();
-        let mut current_id = None;
-
-        input.lines().for_each(|line| {
-            parse_tile_line(&mut tiles, &mut current_tile, &mut current_id, line).unwrap();
-        });
-
-        tiles.push(Tile {
-            id: super::TileId(current_id.unwrap()),
-            tile: current_tile,
-        });
-
-        tiles
+        parsing(&mut DayContext::from_bytes(input, Parts::Both)).unwrap()
     }
 }
 
 pub fn parsing(context: &mut DayContext) -> color_eyre::Result<Input> {
-    let mut tiles = Vec::new();
-    let mut current_tile = Vec::new();
-    let mut current_id = None;
-
-    context.accumulate_str_lines(|_, line| {
-        parse_tile_line(&mut tiles, &mut current_tile, &mut current_id, line)
-    })?;
-
-    tiles.push(Tile {
-        id: TileId(current_id.unwrap()),
-        tile: current_tile,
-    });
-
-    Ok(tiles)
+    context.parse_groups(parse_tile)
 }
 
 pub fn execute(context: &mut DayContext) -> color_eyre::Result<()> {
@@ -1,9 +1,9 @@
-use crate::DayContext;
+use crate::{Answer, DayContext};
 use std::collections::{HashMap, HashSet};
 
 type Input = Vec<Product>;
 
-fn get_allergens(products: &Input) -> HashMap<String, String> {
+fn get_allergens(products: &Input) -> color_eyre::Result<HashMap<String, String>> {
     let mut allergen_candidates = HashMap::new();
     for product in products {
         for allergen in &product.allergens {
@@ -33,7 +33,12 @@ fn get_allergens(products: &Input) -> HashMap<String, String> {
         }
 
         if found.is_empty() {
-            panic!("Could not reduce");
+            let mut remaining: Vec<_> = allergen_candidates.keys().map(String::as_str).collect();
+            remaining.sort_unstable();
+            color_eyre::eyre::bail!(
+                "Could not reduce the ingredients containing {}",
+                remaining.join(", ")
+            );
         }
         for (name, ingredient) in found {
             allergen_candidates.remove(&name);
@@ -43,7 +48,7 @@ fn get_allergens(products: &Input) -> HashMap<String, String> {
             allergens.insert(name, ingredient);
         }
     }
-    allergens
+    Ok(allergens)
 }
 
 fn non_allergenic(products: &Input, allergens: &HashMap<String, String>) -> HashSet<String> {
@@ -58,8 +63,8 @@ fn non_allergenic(products: &Input, allergens: &HashMap<String, String>) -> Hash
     all_products
 }
 
-pub fn part_1(products: Input) -> color_eyre::Result<String> {
-    let allergens = get_allergens(&products);
+pub fn part_1(products: Input) -> color_eyre::Result<Answer> {
+    let allergens = get_allergens(&products)?;
     let non_allergens = non_allergenic(&products, &allergens);
 
     let amount: usize = products
@@ -67,22 +72,23 @@ pub fn part_1(products: Input) -> color_eyre::Result<String> {
         .map(|product| product.ingredients.intersection(&non_allergens).count())
         .sum();
 
-    Ok(format!("Non allergenic appearences: {}", amount))
+    Ok(Answer::new(amount).with_description(format!("Non allergenic appearences: {}", amount)))
 }
 
-pub fn part_2(products: Input) -> color_eyre::Result<String> {
-    let mut allergens: Vec<_> = get_allergens(&products).into_iter().collect();
+pub fn part_2(products: Input) -> color_eyre::Result<Answer> {
+    let mut allergens: Vec<_> = get_allergens(&products)?.into_iter().collect();
     allergens.sort_by(|(a1, _), (a2, _)| a1.cmp(a2));
-    let mut allergens_list = allergens[0].1.to_owned();
-    for (_, allergen) in &allergens[1..] {
-        allergens_list += ",";
-        allergens_list += allergen;
-    }
+    let allergens_list = allergens
+        .iter()
+        .map(|(_, ingredient)| ingredient.as_str())
+        .collect::<Vec<_>>()
+        .join(",");
 
-    Ok(format!("Allergen list is: {}", allergens_list))
+    Ok(Answer::new(&allergens_list)
+        .with_description(format!("Allergen list is: {}", allergens_list)))
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Product {
     ingredients: HashSet<String>,
     allergens: Vec<String>,
@@ -109,7 +115,7 @@ sqjhc mxmxvkd sbzzf (contains fish)"#;
     #[test]
     fn example_non_allergenic() {
         let products = load_example();
-        let allergens = super::get_allergens(&products);
+        let allergens = super::get_allergens(&products).unwrap();
         let non_all = super::non_allergenic(&products, &allergens);
 
         let mut non_all_check = HashSet::new();
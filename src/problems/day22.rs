@@ -1,4 +1,4 @@
-use crate::DayContext;
+use crate::{Answer, DayContext};
 use std::{
     collections::{HashSet, VecDeque},
     hash::{Hash, Hasher},
@@ -6,7 +6,7 @@ use std::{
 
 type Input = (VecDeque<u64>, VecDeque<u64>);
 
-pub fn part_1((mut deck1, mut deck2): Input) -> color_eyre::Result<String> {
+pub fn part_1((mut deck1, mut deck2): Input) -> color_eyre::Result<Answer> {
     while !deck1.is_empty() && !deck2.is_empty() {
         play_round(&mut deck1, &mut deck2);
     }
@@ -17,7 +17,7 @@ pub fn part_1((mut deck1, mut deck2): Input) -> color_eyre::Result<String> {
         ("player1", score(&deck1))
     };
 
-    Ok(format!("Winner is {} with: {}", winner, score))
+    Ok(Answer::new(score).with_description(format!("Winner is {} with: {}", winner, score)))
 }
 
 fn play_round(player1: &mut VecDeque<u64>, player2: &mut VecDeque<u64>) {
@@ -94,11 +94,12 @@ fn score(deck: &VecDeque<u64>) -> u64 {
         .sum()
 }
 
-pub fn part_2((player1, player2): Input) -> color_eyre::Result<String> {
+pub fn part_2((player1, player2): Input) -> color_eyre::Result<Answer> {
     let (winner, deck) = recursive_combat(player1, player2);
     let winner_name = if winner { "player1" } else { "player2" };
 
-    Ok(format!("Winner is {} with: {}", winner_name, score(&deck),))
+    let score = score(&deck);
+    Ok(Answer::new(score).with_description(format!("Winner is {} with: {}", winner_name, score)))
 }
 
 #[cfg(test)]
@@ -126,23 +127,24 @@ mod test {
     }
 }
 
+fn parse_deck(lines: &[String], player: &str) -> color_eyre::Result<VecDeque<u64>> {
+    match lines.split_first() {
+        Some((header, cards)) if header == player => {
+            cards.iter().map(|card| Ok(card.parse()?)).collect()
+        }
+        _ => color_eyre::eyre::bail!("Expected the deck to start with {:?}", player),
+    }
+}
+
 pub fn parsing(context: &mut DayContext) -> color_eyre::Result<Input> {
-    let mut player1 = true;
     let mut deck1 = VecDeque::new();
     let mut deck2 = VecDeque::new();
 
-    context.accumulate_str_lines(|_, line| {
-        if !line.starts_with("Player") {
-            if line.is_empty() {
-                player1 = false;
-            } else {
-                let card = line.parse()?;
-                if player1 {
-                    deck1.push_back(card);
-                } else {
-                    deck2.push_back(card);
-                }
-            }
+    context.accumulate_groups(|index, group| {
+        match index {
+            0 => deck1 = parse_deck(group, "Player 1:")?,
+            1 => deck2 = parse_deck(group, "Player 2:")?,
+            _ => color_eyre::eyre::bail!("Expected only two decks"),
         }
         Ok(())
     })?;
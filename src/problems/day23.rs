@@ -1,19 +1,19 @@
-use crate::DayContext;
+use crate::{Answer, DayContext};
 use std::convert::TryInto;
 
 type Input = [u32; 9];
 
-pub fn part_1(cups: Input) -> color_eyre::Result<String> {
+pub fn part_1(cups: Input) -> color_eyre::Result<Answer> {
     let mut cups = Cups::new(&cups);
     for _ in 0..100 {
         cups.round();
     }
     let ring = cups.ring_starting_at(1);
     let output: String = ring.iter().skip(1).map(|x| x.to_string()).collect();
-    Ok(format!("Ring after 100 moves is: {}", output))
+    Ok(Answer::new(&output).with_description(format!("Ring after 100 moves is: {}", output)))
 }
 
-pub fn part_2(first_cups: Input) -> color_eyre::Result<String> {
+pub fn part_2(first_cups: Input) -> color_eyre::Result<Answer> {
     let mut cups: Vec<u32> = (1..).take(1000000).collect();
     for (c, &f) in cups.iter_mut().zip(first_cups.iter()) {
         *c = f as u32;
@@ -27,10 +27,8 @@ pub fn part_2(first_cups: Input) -> color_eyre::Result<String> {
     let after_1 = cups.ring[1];
     let after_after1 = cups.ring[after_1];
 
-    Ok(format!(
-        "Star cups are: {}",
-        after_after1 as u64 * after_1 as u64
-    ))
+    let stars = after_after1 as u64 * after_1 as u64;
+    Ok(Answer::new(stars).with_description(format!("Star cups are: {}", stars)))
 }
 
 #[derive(Debug)]
@@ -1,4 +1,4 @@
-use crate::DayContext;
+use crate::{Answer, DayContext};
 use std::collections::HashSet;
 
 type Input = Vec<Vec<Direction>>;
@@ -95,9 +95,9 @@ fn colored(directions: &[Vec<Direction>]) -> HashSet<Point> {
     colored
 }
 
-pub fn part_1(input: Input) -> color_eyre::Result<String> {
+pub fn part_1(input: Input) -> color_eyre::Result<Answer> {
     let colored = colored(&input);
-    Ok(format!("Colored points: {}", colored.len()))
+    Ok(Answer::new(colored.len()).with_description(format!("Colored points: {}", colored.len())))
 }
 
 struct GameOfLife {
@@ -144,15 +144,13 @@ impl GameOfLife {
     }
 }
 
-pub fn part_2(directions: Input) -> color_eyre::Result<String> {
+pub fn part_2(directions: Input) -> color_eyre::Result<Answer> {
     let mut game_of_life = GameOfLife::load(&directions);
     for _ in 0..100 {
         game_of_life.step();
     }
-    Ok(format!(
-        "After a bit of game of life: {}",
-        game_of_life.active.len()
-    ))
+    let active = game_of_life.active.len();
+    Ok(Answer::new(active).with_description(format!("After a bit of game of life: {}", active)))
 }
 
 pub fn parsing(context: &mut DayContext) -> color_eyre::Result<Input> {
@@ -0,0 +1,100 @@
+use crate::{Answer, DayContext};
+use std::collections::HashMap;
+
+type Input = PublicKeys;
+
+const MODULUS: u64 = 20201227;
+const SUBJECT: u64 = 7;
+
+#[derive(Clone, Copy, Debug)]
+pub struct PublicKeys {
+    card: u64,
+    door: u64,
+}
+
+fn modpow(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1;
+    base %= modulus;
+    while exponent != 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Finds `x` such that `base^x = target`, with baby-step giant-step
+///
+/// The modulus needs to be prime
+fn discrete_log(base: u64, target: u64, modulus: u64) -> Option<u64> {
+    let steps = (modulus as f64).sqrt().ceil() as u64;
+
+    let mut baby_steps = HashMap::with_capacity(steps as usize);
+    let mut value = 1;
+    for j in 0..steps {
+        baby_steps.entry(value).or_insert(j);
+        value = value * base % modulus;
+    }
+
+    // base^-steps, from Fermat's little theorem
+    let giant_step = modpow(base, modulus - 1 - steps % (modulus - 1), modulus);
+    let mut gamma = target % modulus;
+    for i in 0..steps {
+        if let Some(j) = baby_steps.get(&gamma) {
+            return Some(i * steps + j);
+        }
+        gamma = gamma * giant_step % modulus;
+    }
+
+    None
+}
+
+fn loop_size(public_key: u64) -> color_eyre::Result<u64> {
+    discrete_log(SUBJECT, public_key, MODULUS).ok_or_else(|| {
+        color_eyre::eyre::eyre!("No loop size leads to the public key {}", public_key)
+    })
+}
+
+pub fn part_1(keys: Input) -> color_eyre::Result<Answer> {
+    let card_loop = loop_size(keys.card)?;
+    let encryption_key = modpow(keys.door, card_loop, MODULUS);
+    Ok(Answer::new(encryption_key)
+        .with_description(format!("Encryption key is: {}", encryption_key)))
+}
+
+/// There is no puzzle for the last star
+pub fn part_2(_: Input) -> color_eyre::Result<Answer> {
+    Ok(Answer::new("Merry Christmas"))
+}
+
+pub fn parsing(context: &mut DayContext) -> color_eyre::Result<Input> {
+    let keys = context.parse_lines(|line| Ok(line.parse::<u64>()?))?;
+    match keys.as_slice() {
+        &[card, door] => Ok(PublicKeys { card, door }),
+        _ => color_eyre::eyre::bail!("Expected two public keys, got {}", keys.len()),
+    }
+}
+
+pub fn execute(context: &mut DayContext) -> color_eyre::Result<()> {
+    let input = parsing(context)?;
+    context.execute(input, part_1, part_2)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{loop_size, modpow, MODULUS};
+
+    #[test]
+    fn loop_sizes() {
+        assert_eq!(loop_size(5764801).unwrap(), 8);
+        assert_eq!(loop_size(17807724).unwrap(), 11);
+    }
+
+    #[test]
+    fn encryption_key() {
+        assert_eq!(modpow(17807724, 8, MODULUS), 14897079);
+        assert_eq!(modpow(5764801, 11, MODULUS), 14897079);
+    }
+}
@@ -1,86 +1,255 @@
-use std::collections::HashSet;
+use crate::{Answer, DayContext, Grid};
 
-use crate::DayContext;
+/// Slope of the first part
+const SLOPE: Slope = Slope { right: 3, down: 1 };
 
-pub fn part_1(slope: &Slope) -> color_eyre::Result<String> {
-    let collisions = (0..slope.height)
-        .map(|y| (3 * y % slope.width, y))
-        .filter(|&(x, y)| slope.collides(x, y))
-        .count();
+/// Slopes of the second part
+const SLOPES: [Slope; 5] = [
+    Slope { right: 1, down: 1 },
+    Slope { right: 3, down: 1 },
+    Slope { right: 5, down: 1 },
+    Slope { right: 7, down: 1 },
+    Slope { right: 1, down: 2 },
+];
 
-    Ok(format!(
-        "On the line (3, 1) there were {} collisions",
-        collisions
-    ))
+/// Map of the trees, repeating itself to the right
+///
+/// The trees are stored as a bitset, row by row
+pub struct Forest {
+    width: usize,
+    height: usize,
+    trees: Vec<u64>,
 }
 
-pub fn part_2(slope: &Slope) -> color_eyre::Result<String> {
-    let (slope1, slope2, slope3, slope4) = (0..slope.height)
-        .map(|y| {
-            (
-                y % slope.width,
-                3 * y % slope.width,
-                5 * y % slope.width,
-                7 * y % slope.width,
-                y,
-            )
-        })
-        .fold((0, 0, 0, 0), |(s1, s2, s3, s4), (x1, x2, x3, x4, y)| {
-            (
-                s1 + slope.collides(x1, y) as usize,
-                s2 + slope.collides(x2, y) as usize,
-                s3 + slope.collides(x3, y) as usize,
-                s4 + slope.collides(x4, y) as usize,
-            )
-        });
-    let slope5 = (0..(slope.height / 2))
-        .map(|x| (x % slope.width, x * 2))
-        .filter(|&(x, y)| slope.collides(x, y))
-        .count();
-
-    Ok(format!(
-        "All collisions: {}",
-        slope1 * slope2 * slope3 * slope4 * slope5
-    ))
+impl Forest {
+    fn new(grid: &Grid<bool>) -> Self {
+        let mut trees = vec![0; (grid.width() * grid.height()).div_ceil(64)];
+        for ((x, y), &tree) in grid.iter() {
+            let bit = y * grid.width() + x;
+            trees[bit / 64] |= (tree as u64) << (bit % 64);
+        }
+
+        Self {
+            width: grid.width(),
+            height: grid.height(),
+            trees,
+        }
+    }
+
+    pub fn collides(&self, x: usize, y: usize) -> bool {
+        if y >= self.height || self.width == 0 {
+            return false;
+        }
+        let bit = y * self.width + x % self.width;
+        self.trees[bit / 64] >> (bit % 64) & 1 == 1
+    }
+
+    /// Positions reached going down the slope, from the top left corner
+    fn path(&self, slope: Slope) -> impl Iterator<Item = (usize, usize)> {
+        (0..self.height)
+            .step_by(slope.down)
+            .map(move |y| (y / slope.down * slope.right, y))
+    }
+
+    fn collisions(&self, slope: Slope) -> usize {
+        self.path(slope)
+            .filter(|&(x, y)| self.collides(x, y))
+            .count()
+    }
+
+    /// Draws the map, repeated as far as the path goes, with `O` on the path and `X` on the
+    /// trees hit
+    pub fn render(&self, slope: Slope) -> String {
+        let path: Vec<_> = self.path(slope).collect();
+        let repeats = path.last().map_or(0, |&(x, _)| x / self.width.max(1)) + 1;
+
+        let mut map = String::new();
+        for y in 0..self.height {
+            let on_path = path
+                .binary_search_by_key(&y, |&(_, y)| y)
+                .ok()
+                .map(|i| path[i].0);
+            for x in 0..self.width * repeats {
+                map.push(match (on_path == Some(x), self.collides(x, y)) {
+                    (true, true) => 'X',
+                    (true, false) => 'O',
+                    (false, true) => '#',
+                    (false, false) => '.',
+                });
+            }
+            map.push('\n');
+        }
+        map
+    }
 }
 
+/// Move between two positions checked for trees
+///
+/// The fields are private so that slopes outside of this module come from parsing, which refuses
+/// to go down by 0
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Slope {
-    pub width: usize,
-    pub height: usize,
-    trees: HashSet<(usize, usize)>,
+    right: usize,
+    down: usize,
 }
 
-impl Slope {
-    pub fn collides(&self, x: usize, y: usize) -> bool {
-        self.trees.contains(&(x, y))
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
     }
 }
 
-pub fn parsing(context: &mut DayContext) -> color_eyre::Result<Slope> {
-    let mut trees = HashSet::new();
-    let mut height = 0;
-    let mut width = 0;
+/// Parses `RIGHT,DOWN`, where both can be fractions like `1/2`
+///
+/// Only the positions with whole coordinates are checked, so `1/2,1` is the same as `1,2`
+impl std::str::FromStr for Slope {
+    type Err = color_eyre::eyre::Error;
 
-    context.accumulate_byte_lines(|line_number, line| {
-        for (index, &tree) in line.iter().enumerate() {
-            if tree == b'#' {
-                trees.insert((index, line_number));
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fraction = |s: &str| -> color_eyre::Result<(usize, usize)> {
+            let (numerator, denominator) = match crate::split_string_separator(s, '/') {
+                Some((numerator, denominator)) => (numerator.parse()?, denominator.parse()?),
+                None => (s.parse()?, 1),
+            };
+            if denominator == 0 {
+                color_eyre::eyre::bail!("Division by zero in {}", s);
             }
+            let divisor = gcd(numerator, denominator);
+            Ok((numerator / divisor, denominator / divisor))
+        };
+
+        let (right, down) = crate::split_string_separator(s.trim(), ',')
+            .ok_or_else(|| color_eyre::eyre::eyre!("Expected RIGHT,DOWN, got {}", s))?;
+        let (right, right_denominator) = fraction(right)?;
+        let (down, down_denominator) = fraction(down)?;
+        if down == 0 {
+            color_eyre::eyre::bail!("The slope {} does not go down", s);
+        }
+
+        // The first whole position is reached after a multiple of both denominators
+        let steps = right_denominator / gcd(right_denominator, down_denominator) * down_denominator;
+        Ok(Slope {
+            right: right * steps / right_denominator,
+            down: down * steps / down_denominator,
+        })
+    }
+}
+
+fn count_collisions(forest: &Forest, slopes: &[Slope], render: bool) -> color_eyre::Result<Answer> {
+    let collisions: Vec<_> = slopes
+        .iter()
+        .map(|&slope| forest.collisions(slope))
+        .collect();
+    let product: usize = collisions.iter().product();
+
+    let mut description = match slopes {
+        [slope] => format!(
+            "On the line ({}, {}) there were {} collisions",
+            slope.right, slope.down, product
+        ),
+        _ => format!("All collisions: {}", product),
+    };
+    for (slope, collisions) in slopes.iter().zip(&collisions) {
+        if slopes.len() > 1 {
+            description.push_str(&format!(
+                "\n  - ({}, {}): {}",
+                slope.right, slope.down, collisions
+            ));
+        }
+        if render {
+            description.push('\n');
+            description.push_str(forest.render(*slope).trim_end());
         }
-        width = line.len();
-        height += 1;
+    }
+
+    Ok(Answer::new(product).with_description(description))
+}
+
+pub fn part_1(forest: &Forest) -> color_eyre::Result<Answer> {
+    count_collisions(forest, &[SLOPE], false)
+}
 
-        Ok(())
+pub fn part_2(forest: &Forest) -> color_eyre::Result<Answer> {
+    count_collisions(forest, &SLOPES, false)
+}
+
+pub fn parsing(context: &mut DayContext) -> color_eyre::Result<Forest> {
+    let grid = context.parse_grid(|cell| match cell {
+        b'#' => Ok(true),
+        b'.' => Ok(false),
+        _ => color_eyre::eyre::bail!("Unknown terrain"),
     })?;
 
-    Ok(Slope {
-        width,
-        height,
-        trees,
-    })
+    Ok(Forest::new(&grid))
 }
 
 pub fn execute(context: &mut DayContext) -> color_eyre::Result<()> {
     let input = parsing(context)?;
-    context.execute(&input, part_1, part_2)
+
+    let render = context.options().render;
+    let slopes = context
+        .options()
+        .slopes
+        .iter()
+        .map(|slope| slope.parse())
+        .collect::<color_eyre::Result<Vec<Slope>>>()?;
+    let (part_1_slopes, part_2_slopes) = match slopes.as_slice() {
+        [] => (&[SLOPE][..], &SLOPES[..]),
+        slopes => (slopes, slopes),
+    };
+    context.execute(
+        &input,
+        |forest| count_collisions(forest, part_1_slopes, render),
+        |forest| count_collisions(forest, part_2_slopes, render),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parsing, Slope, SLOPES};
+    use crate::{DayContext, Part};
+
+    const MAP: &str = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#
+";
+
+    #[test]
+    fn collisions() {
+        let forest = parsing(&mut DayContext::from_bytes(MAP, Part::One)).unwrap();
+        let collisions: Vec<_> = SLOPES.iter().map(|&s| forest.collisions(s)).collect();
+        assert_eq!(collisions, vec![2, 7, 3, 4, 2]);
+
+        let render = forest.render(Slope { right: 3, down: 1 });
+        assert_eq!(
+            render.lines().next(),
+            Some("O.##.........##.........##.......")
+        );
+        assert_eq!(render.lines().nth(1).unwrap().chars().nth(3), Some('O'));
+        assert_eq!(render.lines().nth(2).unwrap().chars().nth(6), Some('X'));
+    }
+
+    #[test]
+    fn rational_slopes() {
+        assert_eq!(
+            "1/2,1".parse::<Slope>().unwrap(),
+            Slope { right: 1, down: 2 }
+        );
+        assert_eq!(
+            "3/2,1/3".parse::<Slope>().unwrap(),
+            Slope { right: 9, down: 2 }
+        );
+        assert_eq!("2,2".parse::<Slope>().unwrap(), Slope { right: 2, down: 2 });
+        assert!("1,0".parse::<Slope>().is_err());
+    }
 }
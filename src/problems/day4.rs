@@ -1,161 +1,386 @@
-use crate::DayContext;
+use crate::{Answer, DayContext};
+use std::{fmt::Display, ops::RangeInclusive};
 
-pub fn part_1(passports: &[Option<Passport>]) -> color_eyre::Result<String> {
-    let valid_count = passports
-        .iter()
-        .filter(|passport| passport.is_some())
-        .count();
+/// Rules of the puzzle, in the format read by [`Schema::from_str`](std::str::FromStr)
+const DEFAULT_SCHEMA: &str = "\
+byr required range 1920-2020
+iyr required range 2010-2020
+eyr required range 2020-2030
+hgt required units cm 150-193 in 59-76
+hcl required hex-colour
+ecl required one-of amb blu brn gry grn hzl oth
+pid required digits 9
+cid optional
+";
 
-    Ok(format!("Valid passports: {}", valid_count))
+/// Fields of a passport, in the order they were written
+#[derive(Debug)]
+pub struct Passport {
+    fields: Vec<(String, String)>,
 }
 
-fn parse_valid(passport: &Passport) -> color_eyre::Result<bool> {
-    let byr: u64 = passport.byr.parse()?;
-    if byr < 1920 || byr > 2020 {
-        return Ok(false);
-    }
-
-    let iyr: u64 = passport.iyr.parse()?;
-    if iyr < 2010 || iyr > 2020 {
-        return Ok(false);
-    }
+#[derive(Debug)]
+enum Validator {
+    Any,
+    Range(RangeInclusive<u64>),
+    /// Number followed by one of the units, in the range of that unit
+    Units(Vec<(String, RangeInclusive<u64>)>),
+    HexColour,
+    OneOf(Vec<String>),
+    Digits(usize),
+}
 
-    let eyr: u64 = passport.eyr.parse()?;
-    if eyr < 2020 || eyr > 2030 {
-        return Ok(false);
-    }
+impl Validator {
+    fn parse(words: &[&str]) -> color_eyre::Result<Self> {
+        let range = |range: &str| -> color_eyre::Result<RangeInclusive<u64>> {
+            let (start, end) = crate::split_string_separator(range, '-')
+                .ok_or_else(|| color_eyre::eyre::eyre!("Malformed range: {}", range))?;
+            Ok(start.parse()?..=end.parse()?)
+        };
 
-    match passport.hgt.strip_suffix("cm") {
-        Some(hgt) => {
-            let hgt: u64 = hgt.parse()?;
-            if hgt < 150 || hgt > 193 {
-                return Ok(false);
+        match words {
+            [] => Ok(Validator::Any),
+            ["range", bounds] => Ok(Validator::Range(range(bounds)?)),
+            ["units", units @ ..] if !units.is_empty() && units.len() % 2 == 0 => {
+                Ok(Validator::Units(
+                    units
+                        .chunks(2)
+                        .map(|unit| Ok((unit[0].to_owned(), range(unit[1])?)))
+                        .collect::<color_eyre::Result<_>>()?,
+                ))
             }
+            ["hex-colour"] => Ok(Validator::HexColour),
+            ["one-of", values @ ..] => Ok(Validator::OneOf(
+                values.iter().map(|&v| v.to_owned()).collect(),
+            )),
+            ["digits", count] => Ok(Validator::Digits(count.parse()?)),
+            _ => color_eyre::eyre::bail!("Unknown validator: {}", words.join(" ")),
         }
-        None => match passport.hgt.strip_suffix("in") {
-            Some(hgt) => {
-                let hgt: u64 = hgt.parse()?;
-                if hgt < 59 || hgt > 76 {
-                    return Ok(false);
-                }
-            }
-            _ => return Ok(false),
-        },
     }
 
-    match passport.hcl.strip_prefix('#') {
-        Some(color) if color.len() == 6 => {
-            if color
-                .chars()
-                .any(|c| (c < '0' || c > '9') && (c < 'a' || c > 'f'))
-            {
-                return Ok(false);
+    /// Explains what the value should look like when it is invalid
+    fn check(&self, value: &str) -> Result<(), String> {
+        let in_range = |value: &str, range: &RangeInclusive<u64>| match value.parse() {
+            Ok(number) if range.contains(&number) => Ok(()),
+            _ => Err(format!(
+                "expected a number from {} to {}",
+                range.start(),
+                range.end()
+            )),
+        };
+
+        match self {
+            Validator::Any => Ok(()),
+            Validator::Range(range) => in_range(value, range),
+            Validator::Units(units) => units
+                .iter()
+                .find_map(|(unit, range)| Some((value.strip_suffix(unit.as_str())?, unit, range)))
+                .ok_or_else(|| {
+                    let units: Vec<_> = units.iter().map(|(unit, _)| unit.as_str()).collect();
+                    format!("expected a unit among {}", units.join(", "))
+                })
+                .and_then(|(number, unit, range)| {
+                    in_range(number, range).map_err(|e| format!("{} in {}", e, unit))
+                }),
+            Validator::HexColour => match value.strip_prefix('#') {
+                Some(colour)
+                    if colour.len() == 6
+                        && colour
+                            .bytes()
+                            .all(|c| matches!(c, b'0'..=b'9' | b'a'..=b'f')) =>
+                {
+                    Ok(())
+                }
+                _ => Err("expected a colour like #12ab4f".into()),
+            },
+            Validator::OneOf(values) => {
+                if values.iter().any(|v| v == value) {
+                    Ok(())
+                } else {
+                    Err(format!("expected one of {}", values.join(", ")))
+                }
+            }
+            Validator::Digits(count) => {
+                if value.len() == *count && value.bytes().all(|c| c.is_ascii_digit()) {
+                    Ok(())
+                } else {
+                    Err(format!("expected {} digits", count))
+                }
             }
         }
-        _ => return Ok(false),
     }
+}
 
-    match passport.ecl.as_str() {
-        "amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth" => (),
-        _ => return Ok(false),
-    }
+#[derive(Debug)]
+struct FieldRule {
+    name: String,
+    required: bool,
+    validator: Validator,
+}
+
+/// Fields allowed in a passport, one per line: `<NAME> required|optional [VALIDATOR]`
+///
+/// The validators are `range A-B`, `units <UNIT> A-B…`, `hex-colour`, `one-of <VALUE>…` and
+/// `digits N`, without validator any value is accepted. Lines starting with `#` are comments.
+#[derive(Debug)]
+pub struct Schema {
+    fields: Vec<FieldRule>,
+}
 
-    if passport.pid.len() != 9 {
-        return Ok(false);
+impl std::str::FromStr for Schema {
+    type Err = color_eyre::eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| -> color_eyre::Result<_> {
+                let words: Vec<_> = line.split_whitespace().collect();
+                let required = match words.get(1) {
+                    Some(&"required") => true,
+                    Some(&"optional") => false,
+                    _ => color_eyre::eyre::bail!("Expected required or optional in {:?}", line),
+                };
+                Ok(FieldRule {
+                    name: words[0].to_owned(),
+                    required,
+                    validator: Validator::parse(&words[2..])?,
+                })
+            })
+            .collect::<color_eyre::Result<_>>()?;
+
+        Ok(Self { fields })
     }
-    passport.pid.parse::<u64>()?;
+}
 
-    Ok(true)
+#[derive(Debug, PartialEq, Eq)]
+pub enum Violation {
+    Missing(String),
+    Unknown(String),
+    Duplicate(String),
+    Invalid {
+        field: String,
+        value: String,
+        reason: String,
+    },
 }
 
-pub fn part_2(passports: &[Option<Passport>]) -> color_eyre::Result<String> {
-    let valid_count = passports
-        .into_iter()
-        .filter_map(|x| x.as_ref())
-        .filter(|passport| match parse_valid(passport) {
-            Err(_) => false,
-            Ok(valid) => valid,
-        })
-        .count();
-
-    Ok(format!("Valid passports: {}", valid_count))
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Violation::Missing(field) => write!(f, "{} is missing", field),
+            Violation::Unknown(field) => write!(f, "{} is not a known field", field),
+            Violation::Duplicate(field) => write!(f, "{} appears more than once", field),
+            Violation::Invalid {
+                field,
+                value,
+                reason,
+            } => write!(f, "{} is {:?}, {}", field, value, reason),
+        }
+    }
 }
 
-#[allow(dead_code)]
-pub struct Passport {
-    byr: String,
-    iyr: String,
-    eyr: String,
-    hgt: String,
-    hcl: String,
-    ecl: String,
-    pid: String,
-    cid: Option<String>,
+impl Violation {
+    /// Whether the passport is refused because of it, unknown and repeated fields are only noted
+    pub fn rejects(&self) -> bool {
+        matches!(self, Violation::Missing(_) | Violation::Invalid { .. })
+    }
 }
 
-#[derive(Default)]
-struct PassportBuilder {
-    byr: Option<String>,
-    iyr: Option<String>,
-    eyr: Option<String>,
-    hgt: Option<String>,
-    hcl: Option<String>,
-    ecl: Option<String>,
-    pid: Option<String>,
-    cid: Option<String>,
+impl Schema {
+    /// Lists every rule the passport breaks, the values are only checked with `check_values`
+    ///
+    /// The last value of a repeated field is the one checked
+    pub fn validate(&self, passport: &Passport, check_values: bool) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        for (i, (name, _)) in passport.fields.iter().enumerate() {
+            if !self.fields.iter().any(|rule| &rule.name == name) {
+                violations.push(Violation::Unknown(name.clone()));
+            } else if passport.fields[..i].iter().any(|(other, _)| other == name) {
+                violations.push(Violation::Duplicate(name.clone()));
+            }
+        }
+
+        for rule in &self.fields {
+            match passport.fields.iter().rfind(|(name, _)| name == &rule.name) {
+                None if rule.required => violations.push(Violation::Missing(rule.name.clone())),
+                None => (),
+                Some((_, value)) if check_values => {
+                    if let Err(reason) = rule.validator.check(value) {
+                        violations.push(Violation::Invalid {
+                            field: rule.name.clone(),
+                            value: value.clone(),
+                            reason,
+                        })
+                    }
+                }
+                Some(_) => (),
+            }
+        }
+
+        violations
+    }
 }
 
-impl PassportBuilder {
-    fn add_category(&mut self, category: &str, value: String) {
-        match category {
-            "byr" => self.byr = Some(value),
-            "iyr" => self.iyr = Some(value),
-            "eyr" => self.eyr = Some(value),
-            "hgt" => self.hgt = Some(value),
-            "hcl" => self.hcl = Some(value),
-            "ecl" => self.ecl = Some(value),
-            "pid" => self.pid = Some(value),
-            "cid" => self.cid = Some(value),
-            ukw => eprintln!("Unknown category: {}", ukw),
+/// Counts the passports following the schema
+///
+/// With `explain`, lists the rejected passports and the accepted ones with unknown or repeated
+/// fields
+fn count_valid(
+    passports: &[Passport],
+    schema: &Schema,
+    check_values: bool,
+    explain: bool,
+) -> color_eyre::Result<Answer> {
+    let mut valid_count = 0;
+    let mut rejections = Vec::new();
+    for (i, passport) in passports.iter().enumerate() {
+        let violations = schema.validate(passport, check_values);
+        let rejected = violations.iter().any(Violation::rejects);
+        if !rejected {
+            valid_count += 1;
+        }
+        if explain && !violations.is_empty() {
+            let violations: Vec<_> = violations.iter().map(Violation::to_string).collect();
+            let verdict = if rejected { "" } else { " (accepted)" };
+            rejections.push(format!(
+                "Passport {}{}: {}",
+                i + 1,
+                verdict,
+                violations.join("; ")
+            ));
         }
     }
 
-    fn build(self) -> Option<Passport> {
-        Some(Passport {
-            byr: self.byr?,
-            iyr: self.iyr?,
-            eyr: self.eyr?,
-            hgt: self.hgt?,
-            hcl: self.hcl?,
-            ecl: self.ecl?,
-            pid: self.pid?,
-            cid: self.cid,
-        })
+    let mut description = format!("Valid passports: {}", valid_count);
+    for rejection in rejections {
+        description.push_str("\n  - ");
+        description.push_str(&rejection);
     }
+    Ok(Answer::new(valid_count).with_description(description))
 }
 
-pub fn execute(context: &mut DayContext) -> color_eyre::Result<()> {
-    let mut passports = Vec::new();
-    let mut current_passport = PassportBuilder::default();
+pub fn part_1(passports: &[Passport]) -> color_eyre::Result<Answer> {
+    count_valid(passports, &DEFAULT_SCHEMA.parse()?, false, false)
+}
 
-    context.accumulate_str_lines(|_, line| {
-        if line.len() == 0 {
-            let mut builder = Default::default();
-            std::mem::swap(&mut builder, &mut current_passport);
+pub fn part_2(passports: &[Passport]) -> color_eyre::Result<Answer> {
+    count_valid(passports, &DEFAULT_SCHEMA.parse()?, true, false)
+}
 
-            passports.push(builder.build());
-        } else {
-            for word in line.split_ascii_whitespace() {
+pub fn execute(context: &mut DayContext) -> color_eyre::Result<()> {
+    let passports = context.parse_groups(|lines| {
+        let fields = lines
+            .iter()
+            .flat_map(|line| line.split_ascii_whitespace())
+            .map(|word| {
                 let (category, value) = crate::split_string_separator(word, ':')
                     .ok_or_else(|| color_eyre::eyre::eyre!("Malformed category: {}", word))?;
+                Ok((category.to_owned(), value.to_owned()))
+            })
+            .collect::<color_eyre::Result<_>>()?;
 
-                current_passport.add_category(category, value.to_owned())
-            }
+        Ok(Passport { fields })
+    })?;
+
+    let schema: Schema = match &context.options().schema {
+        Some(path) => std::fs::read_to_string(path)?.parse()?,
+        None => DEFAULT_SCHEMA.parse()?,
+    };
+    let explain = context.options().explain;
+    context.execute(
+        passports.as_ref(),
+        |passports| count_valid(passports, &schema, false, explain),
+        |passports| count_valid(passports, &schema, true, explain),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{count_valid, Passport, Schema, Violation, DEFAULT_SCHEMA};
+
+    fn passport(fields: &str) -> Passport {
+        Passport {
+            fields: fields
+                .split_whitespace()
+                .map(|field| {
+                    let (name, value) = crate::split_string_separator(field, ':').unwrap();
+                    (name.to_owned(), value.to_owned())
+                })
+                .collect(),
         }
+    }
 
-        Ok(())
-    })?;
-    passports.push(current_passport.build());
+    #[test]
+    fn violations() {
+        let schema: Schema = DEFAULT_SCHEMA.parse().unwrap();
+
+        let valid =
+            passport("pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f");
+        assert!(schema.validate(&valid, true).is_empty());
 
-    context.execute(passports.as_ref(), part_1, part_2)
+        let invalid = passport("hcl:dab227 iyr:2012 ecl:brn hgt:182 pid:021572410 eyr:2020 foo:1");
+        assert_eq!(
+            schema.validate(&invalid, true),
+            vec![
+                Violation::Unknown("foo".into()),
+                Violation::Missing("byr".into()),
+                Violation::Invalid {
+                    field: "hgt".into(),
+                    value: "182".into(),
+                    reason: "expected a unit among cm, in".into(),
+                },
+                Violation::Invalid {
+                    field: "hcl".into(),
+                    value: "dab227".into(),
+                    reason: "expected a colour like #12ab4f".into(),
+                },
+            ]
+        );
+        assert_eq!(
+            schema.validate(&invalid, false),
+            vec![
+                Violation::Unknown("foo".into()),
+                Violation::Missing("byr".into())
+            ]
+        );
+
+        assert!("byr mandatory".parse::<Schema>().is_err());
+        assert!("byr required units cm".parse::<Schema>().is_err());
+    }
+
+    #[test]
+    fn extra_fields() {
+        let schema: Schema = DEFAULT_SCHEMA.parse().unwrap();
+
+        let extra = passport(
+            "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f foo:1 byr:1981",
+        );
+        assert_eq!(
+            schema.validate(&extra, true),
+            vec![
+                Violation::Unknown("foo".into()),
+                Violation::Duplicate("byr".into())
+            ]
+        );
+        assert!(!schema.validate(&extra, true).iter().any(Violation::rejects));
+
+        // The last value of a repeated field wins
+        let repeated = passport(
+            "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1800 hcl:#623a2f byr:1980",
+        );
+        let passports = [extra, repeated];
+        let answer = count_valid(&passports, &schema, true, true).unwrap();
+        assert_eq!(answer.value(), "2");
+        assert_eq!(
+            answer.description(),
+            Some(
+                "Valid passports: 2\n  \
+                 - Passport 1 (accepted): foo is not a known field; byr appears more than once\n  \
+                 - Passport 2 (accepted): byr appears more than once"
+            )
+        );
+    }
 }
@@ -1,123 +1,283 @@
-use crate::DayContext;
+use crate::{errors::ColumnError, Answer, DayContext};
+use std::fmt::Display;
 
-type Input<'i> = &'i [(u8, u8)];
+type Input<'i> = &'i [Seat];
 
-fn seat_id((row, col): (u8, u8)) -> u64 {
-    (row as u64 * 8) + col as u64
+/// Layout of the puzzle: `F`/`B` on 7 letters for the row, `L`/`R` on 3 for the column
+pub const PUZZLE_LAYOUT: Layout = Layout {
+    row: Partition {
+        lower: b'F',
+        upper: b'B',
+        width: 7,
+    },
+    col: Partition {
+        lower: b'L',
+        upper: b'R',
+        width: 3,
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Seat {
+    pub row: u64,
+    pub col: u64,
 }
 
-pub fn part_1(seat: Input) -> color_eyre::Result<String> {
-    let max_id = seat
-        .iter()
-        .map(|&x| seat_id(x))
-        .max()
-        .ok_or_else(|| color_eyre::eyre::eyre!("No passes provided"))?;
+/// Letters choosing a half of the remaining seats, one per halving
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Partition {
+    lower: u8,
+    upper: u8,
+    width: usize,
+}
+
+impl Partition {
+    fn decode(&self, letters: &[u8], offset: usize) -> color_eyre::Result<u64> {
+        letters
+            .iter()
+            .enumerate()
+            .try_fold(0, |position, (i, &letter)| match letter {
+                l if l == self.lower => Ok(position << 1),
+                l if l == self.upper => Ok(position << 1 | 1),
+                _ => Err(ColumnError::new(
+                    offset + i,
+                    format!(
+                        "Expected {} or {}, got {}",
+                        self.lower as char, self.upper as char, letter as char
+                    ),
+                )
+                .into()),
+            })
+    }
+
+    fn encode(&self, position: u64, pass: &mut String) {
+        for bit in (0..self.width).rev() {
+            let letter = match position >> bit & 1 {
+                0 => self.lower,
+                _ => self.upper,
+            };
+            pass.push(letter as char);
+        }
+    }
+
+    /// Number of seats
+    fn size(&self) -> u64 {
+        1 << self.width
+    }
+}
+
+/// Letters of a boarding pass: the row partition followed by the column partition
+///
+/// Parsed from `<LOWER><UPPER><WIDTH>,<LOWER><UPPER><WIDTH>`, `FB7,LR3` for the puzzle
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Layout {
+    row: Partition,
+    col: Partition,
+}
+
+impl Layout {
+    pub fn decode(&self, pass: &[u8]) -> color_eyre::Result<Seat> {
+        let width = self.row.width + self.col.width;
+        if pass.len() != width {
+            return Err(ColumnError::new(
+                width.min(pass.len()),
+                format!("Expected {} letters, got {}", width, pass.len()),
+            )
+            .into());
+        }
+
+        let (row, col) = pass.split_at(self.row.width);
+        Ok(Seat {
+            row: self.row.decode(row, 0)?,
+            col: self.col.decode(col, self.row.width)?,
+        })
+    }
+
+    pub fn encode(&self, seat: Seat) -> color_eyre::Result<String> {
+        if seat.row >= self.row.size() || seat.col >= self.col.size() {
+            color_eyre::eyre::bail!(
+                "The seat ({}, {}) is outside of the {}x{} plane",
+                seat.row,
+                seat.col,
+                self.row.size(),
+                self.col.size()
+            );
+        }
+
+        let mut pass = String::with_capacity(self.row.width + self.col.width);
+        self.row.encode(seat.row, &mut pass);
+        self.col.encode(seat.col, &mut pass);
+        Ok(pass)
+    }
+
+    pub fn seat_id(&self, seat: Seat) -> u64 {
+        seat.row * self.col.size() + seat.col
+    }
+
+    pub fn seat(&self, id: u64) -> Seat {
+        Seat {
+            row: id / self.col.size(),
+            col: id % self.col.size(),
+        }
+    }
+}
+
+impl std::str::FromStr for Layout {
+    type Err = color_eyre::eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let partition = |s: &str| -> color_eyre::Result<Partition> {
+            match s.as_bytes() {
+                &[lower, upper, ref width @ ..]
+                    if lower != upper && lower.is_ascii() && upper.is_ascii() =>
+                {
+                    Ok(Partition {
+                        lower,
+                        upper,
+                        width: std::str::from_utf8(width)?.parse()?,
+                    })
+                }
+                _ => color_eyre::eyre::bail!("Expected two letters and a width, got {}", s),
+            }
+        };
+
+        let (row, col) = crate::split_string_separator(s, ',')
+            .ok_or_else(|| color_eyre::eyre::eyre!("Expected ROW,COLUMN, got {}", s))?;
+        let layout = Layout {
+            row: partition(row)?,
+            col: partition(col)?,
+        };
+        // The seat ids need to fit in an u64
+        if layout.row.width + layout.col.width >= 64 {
+            color_eyre::eyre::bail!("The layout {} has too many letters", s);
+        }
+        Ok(layout)
+    }
+}
 
-    Ok(format!("Highest seat id is: {}", max_id))
+impl Display for Layout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}{},{}{}{}",
+            self.row.lower as char,
+            self.row.upper as char,
+            self.row.width,
+            self.col.lower as char,
+            self.col.upper as char,
+            self.col.width
+        )
+    }
 }
 
-pub fn part_2(seat: Input) -> color_eyre::Result<String> {
-    let mut ids: Vec<u64> = seat.iter().map(|&x| seat_id(x)).collect();
+/// Ids that are missing while both their neighbours are present
+fn gaps(seats: Input, layout: &Layout) -> Vec<u64> {
+    let mut ids: Vec<u64> = seats.iter().map(|&seat| layout.seat_id(seat)).collect();
     ids.sort_unstable();
 
-    let missing_id = ids
-        .windows(2)
+    ids.windows(2)
         .filter(|x| x[0] + 2 == x[1])
         .map(|x| x[0] + 1)
-        .nth(0)
-        .ok_or_else(|| color_eyre::eyre::eyre!("Did not find a missing id"))?;
+        .collect()
+}
 
-    Ok(format!("Missing id is: {}", missing_id))
+fn highest_id(seats: Input, layout: &Layout) -> color_eyre::Result<Answer> {
+    let max_id = seats
+        .iter()
+        .map(|&seat| layout.seat_id(seat))
+        .max()
+        .ok_or_else(|| color_eyre::eyre::eyre!("No passes provided"))?;
+
+    Ok(Answer::new(max_id).with_description(format!("Highest seat id is: {}", max_id)))
 }
 
-#[derive(Clone, Copy, Debug)]
-enum RowSelection {
-    Front,
-    Back,
+/// Finds the first gap, or lists all of them with `all_gaps`
+fn missing_ids(seats: Input, layout: &Layout, all_gaps: bool) -> color_eyre::Result<Answer> {
+    let mut gaps = gaps(seats, layout);
+    if !all_gaps {
+        gaps.truncate(1);
+    }
+    if gaps.is_empty() {
+        color_eyre::eyre::bail!("Did not find a missing id");
+    }
+
+    let passes = gaps
+        .iter()
+        .map(|&id| Ok(format!("{} ({})", id, layout.encode(layout.seat(id))?)))
+        .collect::<color_eyre::Result<Vec<_>>>()?;
+    let ids: Vec<_> = gaps.iter().map(u64::to_string).collect();
+    Ok(Answer::new(ids.join(",")).with_description(format!(
+        "Missing id{} {}: {}",
+        if gaps.len() > 1 { "s" } else { "" },
+        if gaps.len() > 1 { "are" } else { "is" },
+        passes.join(", ")
+    )))
 }
 
-#[derive(Clone, Copy, Debug)]
-enum ColSelection {
-    Right,
-    Left,
+pub fn part_1(seats: Input) -> color_eyre::Result<Answer> {
+    highest_id(seats, &PUZZLE_LAYOUT)
 }
 
-struct BoardingPass {
-    row: [RowSelection; 7],
-    col: [ColSelection; 3],
+pub fn part_2(seats: Input) -> color_eyre::Result<Answer> {
+    missing_ids(seats, &PUZZLE_LAYOUT, false)
 }
 
-impl BoardingPass {
-    fn seat(&self) -> (u8, u8) {
-        let mut row = 0..128;
-        for r in &self.row {
-            let half = (row.start + row.end) / 2;
-            match r {
-                RowSelection::Back => row.start = half,
-                RowSelection::Front => row.end = half,
-            }
-        }
+pub fn parsing(context: &mut DayContext, layout: &Layout) -> color_eyre::Result<Vec<Seat>> {
+    context.parse_byte_lines(|line| layout.decode(line))
+}
 
-        let mut col = 0..8;
-        for c in &self.col {
-            let half = (col.start + col.end) / 2;
-            match c {
-                ColSelection::Right => col.start = half,
-                ColSelection::Left => col.end = half,
-            }
-        }
+pub fn execute(context: &mut DayContext) -> color_eyre::Result<()> {
+    let layout = match &context.options().layout {
+        Some(layout) => layout.parse()?,
+        None => PUZZLE_LAYOUT,
+    };
+    let all_gaps = context.options().all_gaps;
 
-        (row.start, col.start)
-    }
+    let input = parsing(context, &layout)?;
+    context.execute(
+        input.as_ref(),
+        |seats| highest_id(seats, &layout),
+        |seats| missing_ids(seats, &layout, all_gaps),
+    )
 }
 
 #[cfg(test)]
 mod test {
-    use super::{BoardingPass, ColSelection, RowSelection};
+    use super::{gaps, Layout, Seat, PUZZLE_LAYOUT};
 
     #[test]
     fn test_seat() {
-        let pass = BoardingPass {
-            row: [
-                RowSelection::Front,
-                RowSelection::Back,
-                RowSelection::Front,
-                RowSelection::Back,
-                RowSelection::Back,
-                RowSelection::Front,
-                RowSelection::Front,
-            ],
-            col: [ColSelection::Right, ColSelection::Left, ColSelection::Right],
-        };
+        let seat = PUZZLE_LAYOUT.decode(b"FBFBBFFRLR").unwrap();
+        assert_eq!(seat, Seat { row: 44, col: 5 });
+        assert_eq!(PUZZLE_LAYOUT.seat_id(seat), 357);
+        assert_eq!(PUZZLE_LAYOUT.encode(seat).unwrap(), "FBFBBFFRLR");
+        assert_eq!(
+            PUZZLE_LAYOUT.seat(820),
+            PUZZLE_LAYOUT.decode(b"BBFFBBFRLL").unwrap()
+        );
 
-        assert_eq!(pass.seat(), (44, 5))
+        assert!(PUZZLE_LAYOUT.decode(b"FBFBBFFRL").is_err());
+        assert!(PUZZLE_LAYOUT.decode(b"FBFBXFFRLR").is_err());
+        assert!(PUZZLE_LAYOUT.encode(Seat { row: 128, col: 0 }).is_err());
     }
-}
-
-pub fn parsing(context: &mut DayContext) -> color_eyre::Result<Vec<(u8, u8)>> {
-    context.parse_byte_lines(|line| {
-        if line.len() != 10 {
-            color_eyre::eyre::bail!("Line of invalid length: {}", line)
-        }
-        let mut row = [RowSelection::Front; 7];
-        for (row, _) in row.iter_mut().zip(line.iter()).filter(|&(_, &l)| l == b'B') {
-            *row = RowSelection::Back;
-        }
 
-        let mut col = [ColSelection::Left; 3];
-        for (col, _) in col
-            .iter_mut()
-            .zip(line[7..].iter())
-            .filter(|&(_, &l)| l == b'R')
-        {
-            *col = ColSelection::Right;
-        }
-
-        Ok(BoardingPass { row, col }.seat())
-    })
-}
+    #[test]
+    fn custom_layout() {
+        let layout: Layout = "014,ab2".parse().unwrap();
+        assert_eq!(layout.to_string(), "014,ab2");
+        let seat = layout.decode(b"0110ba").unwrap();
+        assert_eq!(seat, Seat { row: 6, col: 2 });
+        assert_eq!(layout.seat_id(seat), 26);
+        assert_eq!(layout.encode(seat).unwrap(), "0110ba");
+        assert!("FF7,LR3".parse::<Layout>().is_err());
+    }
 
-pub fn execute(context: &mut DayContext) -> color_eyre::Result<()> {
-    let input = parsing(context)?;
-    context.execute(input.as_ref(), part_1, part_2)
+    #[test]
+    fn all_gaps() {
+        let seats: Vec<_> = [1, 2, 4, 5, 7, 10]
+            .iter()
+            .map(|&id| PUZZLE_LAYOUT.seat(id))
+            .collect();
+        assert_eq!(gaps(&seats, &PUZZLE_LAYOUT), vec![3, 6]);
+    }
 }
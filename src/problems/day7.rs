@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::DayContext;
+use crate::{Answer, DayContext};
 use petgraph::{
     graph::{DiGraph, NodeIndex},
     visit::{Dfs, EdgeRef, Walker},
@@ -8,20 +8,21 @@ use petgraph::{
 
 type Input = (NodeIndex, DiGraph<(), u64>);
 
-pub fn part_1((shiny_gold, graph): &Input) -> color_eyre::Result<String> {
+pub fn part_1((shiny_gold, graph): &Input) -> color_eyre::Result<Answer> {
     let mut graph = graph.clone();
     graph.reverse();
     let containing_count = Dfs::new(&graph, *shiny_gold).iter(&graph).count() - 1;
-    Ok(format!(
+    Ok(Answer::new(containing_count).with_description(format!(
         "Number of bags that can contain shiny gold: {:?}",
         containing_count
-    ))
+    )))
 }
 
-pub fn part_2((shiny_gold, graph): &Input) -> color_eyre::Result<String> {
+pub fn part_2((shiny_gold, graph): &Input) -> color_eyre::Result<Answer> {
     let mut amount = HashMap::new();
     let shiny_gold = recurse_amount(&mut amount, *shiny_gold, graph);
-    Ok(format!("Shiny gold will contain: {} bags", shiny_gold))
+    Ok(Answer::new(shiny_gold)
+        .with_description(format!("Shiny gold will contain: {} bags", shiny_gold)))
 }
 
 fn recurse_amount(
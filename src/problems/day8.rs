@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::DayContext;
+use crate::{Answer, DayContext};
 
 type Input<'i> = &'i [Instr];
 
@@ -17,7 +17,7 @@ pub struct Instr {
     arg: i64,
 }
 
-pub fn part_1(code: Input) -> color_eyre::Result<String> {
+pub fn part_1(code: Input) -> color_eyre::Result<Answer> {
     let mut acc = 0;
     let mut ip = 0;
     let mut visited = HashSet::new();
@@ -42,10 +42,10 @@ pub fn part_1(code: Input) -> color_eyre::Result<String> {
         }
     };
 
-    Ok(format!("Accumulator is at: {}", final_value))
+    Ok(Answer::new(final_value).with_description(format!("Accumulator is at: {}", final_value)))
 }
 
-pub fn part_2(code: Input) -> color_eyre::Result<String> {
+pub fn part_2(code: Input) -> color_eyre::Result<Answer> {
     let mut patched = code.to_owned();
     for (index, instr) in code.iter().enumerate().filter(|(_, i)| match i.op {
         OpCode::Acc => false,
@@ -63,7 +63,9 @@ pub fn part_2(code: Input) -> color_eyre::Result<String> {
 
         loop {
             if ip == patched.len() {
-                return Ok(format!("Patched accumulator is: {}", acc))
+                return Ok(
+                    Answer::new(acc).with_description(format!("Patched accumulator is: {}", acc))
+                );
             }
 
             if visited.contains(&ip) {
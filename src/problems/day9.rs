@@ -1,4 +1,4 @@
-use crate::DayContext;
+use crate::{Answer, DayContext};
 
 type Input<'i> = &'i [u64];
 
@@ -24,9 +24,9 @@ fn first_invalid(input: Input) -> color_eyre::Result<u64> {
         .ok_or_else(|| color_eyre::eyre::eyre!("No invalid num found"))
 }
 
-pub fn part_1(input: Input) -> color_eyre::Result<String> {
+pub fn part_1(input: Input) -> color_eyre::Result<Answer> {
     let invalid = first_invalid(input)?;
-    Ok(format!("First invalid: {}", invalid))
+    Ok(Answer::new(invalid).with_description(format!("First invalid: {}", invalid)))
 }
 
 struct GrowingWindows<'i, T> {
@@ -57,7 +57,7 @@ impl<'i, T> Iterator for GrowingWindows<'i, T> {
     }
 }
 
-pub fn part_2(input: Input) -> color_eyre::Result<String> {
+pub fn part_2(input: Input) -> color_eyre::Result<Answer> {
     let invalid = first_invalid(input)?;
 
     for i in 0..input.len() {
@@ -79,10 +79,11 @@ pub fn part_2(input: Input) -> color_eyre::Result<String> {
                }
             }
 
-            return Ok(format!("Weakness is: {}", min + max));
+            let weakness = min + max;
+            return Ok(Answer::new(weakness).with_description(format!("Weakness is: {}", weakness)));
         }
     }
-    todo!()
+    color_eyre::eyre::bail!("No contiguous set sums to {}", invalid)
 }
 
 pub fn parsing(context: &mut DayContext) -> color_eyre::Result<Vec<u64>> {
@@ -1,3 +1,6 @@
+use crate::DayContext;
+use color_eyre::eyre::Context;
+
 #[cfg(feature = "day1")]
 pub mod day1;
 #[cfg(feature = "day2")]
@@ -46,3 +49,158 @@ pub mod day22;
 pub mod day23;
 #[cfg(feature = "day24")]
 pub mod day24;
+#[cfg(feature = "day25")]
+pub mod day25;
+
+pub struct Day {
+    pub number: u8,
+    pub execute: fn(&mut DayContext) -> color_eyre::Result<()>,
+}
+
+impl Day {
+    /// Executes the day, errors are reported with the day they come from
+    pub fn run(&self, context: &mut DayContext) -> color_eyre::Result<()> {
+        (self.execute)(context).wrap_err_with(|| format!("Day {} failed", self.number))
+    }
+}
+
+/// Every day enabled through its `dayN` feature, in calendar order
+pub const DAYS: &[Day] = &[
+    #[cfg(feature = "day1")]
+    Day {
+        number: 1,
+        execute: day1::execute,
+    },
+    #[cfg(feature = "day2")]
+    Day {
+        number: 2,
+        execute: day2::execute,
+    },
+    #[cfg(feature = "day3")]
+    Day {
+        number: 3,
+        execute: day3::execute,
+    },
+    #[cfg(feature = "day4")]
+    Day {
+        number: 4,
+        execute: day4::execute,
+    },
+    #[cfg(feature = "day5")]
+    Day {
+        number: 5,
+        execute: day5::execute,
+    },
+    #[cfg(feature = "day6")]
+    Day {
+        number: 6,
+        execute: day6::execute,
+    },
+    #[cfg(feature = "day7")]
+    Day {
+        number: 7,
+        execute: day7::execute,
+    },
+    #[cfg(feature = "day8")]
+    Day {
+        number: 8,
+        execute: day8::execute,
+    },
+    #[cfg(feature = "day9")]
+    Day {
+        number: 9,
+        execute: day9::execute,
+    },
+    #[cfg(feature = "day10")]
+    Day {
+        number: 10,
+        execute: day10::execute,
+    },
+    #[cfg(feature = "day11")]
+    Day {
+        number: 11,
+        execute: day11::execute,
+    },
+    #[cfg(feature = "day12")]
+    Day {
+        number: 12,
+        execute: day12::execute,
+    },
+    #[cfg(feature = "day13")]
+    Day {
+        number: 13,
+        execute: day13::execute,
+    },
+    #[cfg(feature = "day14")]
+    Day {
+        number: 14,
+        execute: day14::execute,
+    },
+    #[cfg(feature = "day15")]
+    Day {
+        number: 15,
+        execute: day15::execute,
+    },
+    #[cfg(feature = "day16")]
+    Day {
+        number: 16,
+        execute: day16::execute,
+    },
+    #[cfg(feature = "day17")]
+    Day {
+        number: 17,
+        execute: day17::execute,
+    },
+    #[cfg(feature = "day18")]
+    Day {
+        number: 18,
+        execute: day18::execute,
+    },
+    #[cfg(feature = "day19")]
+    Day {
+        number: 19,
+        execute: day19::execute,
+    },
+    #[cfg(feature = "day20")]
+    Day {
+        number: 20,
+        execute: day20::execute,
+    },
+    #[cfg(feature = "day21")]
+    Day {
+        number: 21,
+        execute: day21::execute,
+    },
+    #[cfg(feature = "day22")]
+    Day {
+        number: 22,
+        execute: day22::execute,
+    },
+    #[cfg(feature = "day23")]
+    Day {
+        number: 23,
+        execute: day23::execute,
+    },
+    #[cfg(feature = "day24")]
+    Day {
+        number: 24,
+        execute: day24::execute,
+    },
+    #[cfg(feature = "day25")]
+    Day {
+        number: 25,
+        execute: day25::execute,
+    },
+];
+
+pub fn get_day(number: u8) -> color_eyre::Result<&'static Day> {
+    match DAYS.iter().find(|day| day.number == number) {
+        Some(day) => Ok(day),
+        None if (1..=25).contains(&number) => color_eyre::eyre::bail!(
+            "Day {} is not compiled in, enable it with `--features day{}`",
+            number,
+            number
+        ),
+        None => color_eyre::eyre::bail!("There is no day {} in the calendar", number),
+    }
+}
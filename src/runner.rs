@@ -0,0 +1,332 @@
+use crate::{
+    answers::{Answers, Mismatch},
+    format_time,
+    inputs::{InputCache, InputKind, MissingInput},
+    output::Record,
+    problems, DayContext, Part, Parts, Timing,
+};
+use std::{
+    fs::File,
+    io::BufReader,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    time::{Duration, Instant},
+};
+
+pub enum Outcome {
+    Solved {
+        answer: String,
+        timing: Timing,
+        /// Whether an expected answer was available to compare with
+        checked: bool,
+    },
+    Mismatch {
+        mismatch: Mismatch,
+        timing: Timing,
+    },
+    MissingInput,
+    Failed(color_eyre::Report),
+    /// An earlier error prevented this part from running
+    Skipped,
+}
+
+pub struct PartResult {
+    pub day: u8,
+    pub part: Part,
+    pub outcome: Outcome,
+    /// Wall-clock time of the whole day, from reading its input to the end of its last part
+    pub wall: Duration,
+}
+
+impl PartResult {
+    pub fn is_failure(&self) -> bool {
+        matches!(self.outcome, Outcome::Mismatch { .. } | Outcome::Failed(_))
+    }
+
+    pub fn record(&self) -> Record {
+        let record = match &self.outcome {
+            Outcome::Solved { answer, timing, .. } => {
+                Record::new(self.day, self.part, Some(answer.clone()), Some(timing))
+            }
+            Outcome::Mismatch { mismatch, timing } => Record {
+                error: Some(mismatch.to_string()),
+                ..Record::new(
+                    self.day,
+                    self.part,
+                    Some(mismatch.actual.clone()),
+                    Some(timing),
+                )
+            },
+            Outcome::MissingInput => Record {
+                error: Some("missing input".into()),
+                ..Record::new(self.day, self.part, None, None)
+            },
+            Outcome::Failed(e) => Record {
+                error: Some(format!("{:#}", e)),
+                ..Record::new(self.day, self.part, None, None)
+            },
+            Outcome::Skipped => Record {
+                error: Some("skipped".into()),
+                ..Record::new(self.day, self.part, None, None)
+            },
+        };
+
+        match self.outcome {
+            Outcome::MissingInput => record,
+            _ => Record {
+                wall_ns: Some(self.wall.as_nanos()),
+                ..record
+            },
+        }
+    }
+}
+
+fn solved(part: Part, answer: String, timing: Timing, expected: Option<&Answers>) -> Outcome {
+    let expected = match expected {
+        Some(expected) if expected.expected(part).is_some() => expected,
+        _ => {
+            return Outcome::Solved {
+                answer,
+                timing,
+                checked: false,
+            }
+        }
+    };
+
+    match expected.check(part, &answer) {
+        Some(mismatch) => Outcome::Mismatch { mismatch, timing },
+        None => Outcome::Solved {
+            answer,
+            timing,
+            checked: true,
+        },
+    }
+}
+
+/// Message of a caught panic, when it is a string like the ones of `panic!`
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause")
+}
+
+fn run_day(day: &problems::Day, cache: &InputCache, kind: &InputKind, check: bool) -> Vec<Outcome> {
+    match cache.resolve(day.number, kind) {
+        Ok(path) => run_input(day, &path, check),
+        Err(e) if e.is::<MissingInput>() => vec![Outcome::MissingInput, Outcome::MissingInput],
+        Err(e) => vec![Outcome::Failed(e), Outcome::Skipped],
+    }
+}
+
+/// Parses the input once and runs both parts on it, giving the outcome of each part
+///
+/// Parts that could not run because of an earlier error are skipped
+pub fn run_input(day: &problems::Day, path: &Path, check: bool) -> Vec<Outcome> {
+    let input = match File::open(path) {
+        Ok(input) => input,
+        Err(e) => return vec![Outcome::Failed(e.into()), Outcome::Skipped],
+    };
+
+    let expected = if check {
+        if Answers::path(path).exists() {
+            match Answers::load(path) {
+                Ok(answers) => Some(answers),
+                Err(e) => return vec![Outcome::Failed(e), Outcome::Skipped],
+            }
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    let mut context = DayContext::from_reader(BufReader::new(input), Parts::Both).with_path(path);
+    let mut error = day.run(&mut context).err();
+
+    Part::ALL
+        .iter()
+        .map(|&part| match context.answers[part as usize].take() {
+            Some(answer) => solved(
+                part,
+                answer.value().to_owned(),
+                context.timing,
+                expected.as_ref(),
+            ),
+            None => match error.take() {
+                Some(e) => Outcome::Failed(e),
+                None => Outcome::Skipped,
+            },
+        })
+        .collect()
+}
+
+/// Runs both parts of every compiled day on their input in the cache, on `jobs` threads
+///
+/// The results are in day order whatever order the days finish in. With a single job the days
+/// run one after the other on the current thread, which gives the most accurate timings.
+///
+/// A day that panics fails without stopping the others.
+///
+/// When `check` is set the answers are compared to the ones in `<INPUT>.answers`
+pub fn run_all(
+    cache: &InputCache,
+    kind: &InputKind,
+    check: bool,
+    jobs: usize,
+) -> color_eyre::Result<Vec<PartResult>> {
+    color_eyre::eyre::ensure!(jobs > 0, "At least one job is needed to run the days");
+
+    let next_day = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    let worker = |sender: mpsc::Sender<_>| {
+        while let Some(day) = problems::DAYS.get(next_day.fetch_add(1, Ordering::Relaxed)) {
+            let start = Instant::now();
+            let outcomes =
+                panic::catch_unwind(AssertUnwindSafe(|| run_day(day, cache, kind, check)))
+                    .unwrap_or_else(|payload| {
+                        let error = color_eyre::eyre::eyre!(
+                            "Day {} panicked: {}",
+                            day.number,
+                            panic_message(payload.as_ref())
+                        );
+                        vec![Outcome::Failed(error), Outcome::Skipped]
+                    });
+            // The receiver outlives the workers
+            sender.send((day, outcomes, start.elapsed())).unwrap();
+        }
+    };
+
+    let jobs = jobs.min(problems::DAYS.len());
+    if jobs == 1 {
+        worker(sender);
+    } else {
+        std::thread::scope(|scope| {
+            for _ in 0..jobs {
+                let sender = sender.clone();
+                scope.spawn(move || worker(sender));
+            }
+        });
+        drop(sender);
+    }
+
+    let mut days: Vec<_> = receiver.into_iter().collect();
+    days.sort_by_key(|(day, ..)| day.number);
+    Ok(days
+        .into_iter()
+        .flat_map(|(day, outcomes, wall)| {
+            Part::ALL
+                .iter()
+                .zip(outcomes)
+                .map(move |(&part, outcome)| PartResult {
+                    day: day.number,
+                    part,
+                    outcome,
+                    wall,
+                })
+        })
+        .collect())
+}
+
+pub fn print_summary(results: &[PartResult], check: bool) {
+    let mut header = vec!["Day", "Part", "Answer", "Parsing", "Execution", "Wall"];
+    if check {
+        header.push("Check");
+    }
+    let header: Vec<String> = header.into_iter().map(String::from).collect();
+
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .map(|result| {
+            let (answer, timing, status) = match &result.outcome {
+                Outcome::Solved {
+                    answer,
+                    timing,
+                    checked,
+                } => (
+                    answer.clone(),
+                    Some(timing),
+                    if *checked { "ok" } else { "-" },
+                ),
+                Outcome::Mismatch { mismatch, timing } => {
+                    (mismatch.actual.clone(), Some(timing), "WRONG")
+                }
+                Outcome::MissingInput => ("missing input".into(), None, "-"),
+                Outcome::Failed(e) => (format!("error: {:#}", e), None, "-"),
+                Outcome::Skipped => ("skipped".into(), None, "-"),
+            };
+
+            let mut row = vec![
+                result.day.to_string(),
+                result.part.to_string(),
+                answer,
+                timing
+                    .and_then(|t| t.parsing)
+                    .map(format_time)
+                    .unwrap_or_default(),
+                timing
+                    .and_then(|t| t.execution(result.part))
+                    .map(format_time)
+                    .unwrap_or_default(),
+                // Both parts ran in the same task, its time is only shown once
+                match (result.part, &result.outcome) {
+                    (_, Outcome::MissingInput) | (Part::Two, _) => String::new(),
+                    (Part::One, _) => format_time(result.wall),
+                },
+            ];
+            if check {
+                row.push(status.into());
+            }
+            row
+        })
+        .collect();
+
+    let mut widths = vec![0; header.len()];
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let print_row = |row: &[String]| {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, &width))| match column {
+                // The answer is the only left aligned column
+                2 => format!("{:<width$}", cell, width = width),
+                _ => format!("{:>width$}", cell, width = width),
+            })
+            .collect::<Vec<_>>()
+            .join(" | ");
+        println!("{}", line.trim_end())
+    };
+
+    print_row(&header);
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|&w| "-".repeat(w))
+            .collect::<Vec<_>>()
+            .join("-+-")
+    );
+    for row in &rows {
+        print_row(row);
+    }
+
+    for result in results {
+        if let Outcome::Mismatch { mismatch, .. } = &result.outcome {
+            println!();
+            println!("Day {}: {}", result.day, mismatch);
+            println!("{}", mismatch.diff());
+        }
+    }
+}
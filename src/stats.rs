@@ -0,0 +1,88 @@
+use crate::{format_time, Part, Timing};
+use std::{fmt::Display, time::Duration};
+
+/// Summary of the durations of repeated runs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    /// Sample standard deviation, zero for a single run
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let middle = sorted.len() / 2;
+        let median = if sorted.len() % 2 == 1 {
+            sorted[middle]
+        } else {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        };
+
+        let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / nanos.len() as f64;
+        let variance = if nanos.len() > 1 {
+            nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / (nanos.len() - 1) as f64
+        } else {
+            0.
+        };
+
+        Some(Self {
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        })
+    }
+
+    pub fn parsing(samples: &[Timing]) -> Option<Self> {
+        let parsing: Vec<_> = samples.iter().filter_map(|t| t.parsing).collect();
+        Self::new(&parsing)
+    }
+
+    pub fn execution(samples: &[Timing], part: Part) -> Option<Self> {
+        let execution: Vec<_> = samples.iter().filter_map(|t| t.execution(part)).collect();
+        Self::new(&execution)
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {}, median {}, mean {}, stddev {}",
+            format_time(self.min),
+            format_time(self.median),
+            format_time(self.mean),
+            format_time(self.stddev)
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Stats;
+    use std::time::Duration;
+
+    #[test]
+    fn summary() {
+        let samples: Vec<_> = [4, 1, 3, 2, 5]
+            .iter()
+            .map(|&n| Duration::from_nanos(n))
+            .collect();
+        let stats = Stats::new(&samples).unwrap();
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.median, Duration::from_nanos(3));
+        assert_eq!(stats.mean, Duration::from_nanos(3));
+        assert_eq!(stats.stddev, Duration::from_nanos(2));
+
+        assert!(Stats::new(&[]).is_none());
+    }
+}
@@ -0,0 +1,48 @@
+//! Runs every `fixtures/dayN/*.txt` through the compiled days, checking the answers against
+//! the `.txt.answers` file next to it
+
+use aoc_2020::{
+    problems,
+    runner::{run_input, Outcome},
+    Part,
+};
+use std::{ffi::OsStr, path::Path};
+
+#[test]
+fn fixtures() {
+    let mut failures = Vec::new();
+
+    for day in problems::DAYS {
+        let folder = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures")
+            .join(format!("day{}", day.number));
+        let entries = match std::fs::read_dir(&folder) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        let mut fixtures: Vec<_> = entries
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension() == Some(OsStr::new("txt")))
+            .collect();
+        fixtures.sort();
+
+        for fixture in fixtures {
+            for (part, outcome) in Part::ALL.iter().zip(run_input(day, &fixture, true)) {
+                let failure = match outcome {
+                    Outcome::Solved { checked: true, .. } => continue,
+                    Outcome::Solved { answer, .. } => format!("no expected answer, got {}", answer),
+                    Outcome::Mismatch { mismatch, .. } => {
+                        format!("{}\n{}", mismatch, mismatch.diff())
+                    }
+                    Outcome::MissingInput => "missing input".into(),
+                    Outcome::Failed(e) => format!("{:#}", e),
+                    Outcome::Skipped => "skipped".into(),
+                };
+                failures.push(format!("{:?} part {}: {}", fixture, part, failure));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}